@@ -7,7 +7,7 @@ use rtc::{
 };
 
 fn main() {
-    let mut wall_back = Object::new_plane()
+    let mut wall_back = Object::new_rectangle(12.0, 6.0)
         .with_transformation(translation(1.0, 1.0, 5.0) * rotation_x(PI / 2.0))
         .with_color(Color::black());
     /*.with_pattern(Pattern::with_type(PatternType::ring_pattern(
@@ -20,7 +20,7 @@ fn main() {
     wall_back.material.diffuse = 1.0;
     wall_back.material.shininess = 1600.0;
 
-    let mut wall_left = Object::new_plane()
+    let mut wall_left = Object::new_rectangle(6.0, 6.0)
         .with_transformation(translation(-3.0, 1.0, 1.0) * rotation_z(PI / 2.0))
         .with_color(Color::black());
     wall_left.material.specular = 7.0;
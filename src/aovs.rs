@@ -0,0 +1,80 @@
+use crate::color::Color;
+use crate::intersection::Computations;
+use crate::{Canvas, OutputTransform, Point, Vector};
+
+//Extra buffers rendered alongside the image, from the first surface seen
+// through each pixel. Each is a Canvas, so it can be written like any
+// other image; float formats (write_exr, write_pfm) keep the raw values.
+pub struct Aovs {
+    //Distance from the camera to the hit, infinite where nothing was hit.
+    pub depth: Canvas,
+    //World space normal facing the camera, each component in [-1, 1].
+    pub normal: Canvas,
+    //Surface color before lighting, see Material::color_at.
+    pub albedo: Canvas,
+    //Index of the hit object in World::objects plus one, 0 where nothing
+    // was hit.
+    pub object_id: Canvas,
+    //World space position of the hit.
+    pub position: Canvas,
+}
+
+impl Aovs {
+    pub fn new(width: usize, height: usize) -> Self {
+        //everything but albedo holds data rather than colors
+        let data = || {
+            Canvas::new(width, height)
+                .with_output_transform(OutputTransform::new().with_srgb(false))
+        };
+        let mut depth = data();
+        for value in depth.pixels().iter_mut() {
+            *value = gray(f64::INFINITY);
+        }
+
+        Self {
+            depth,
+            normal: data(),
+            albedo: Canvas::new(width, height),
+            object_id: data(),
+            position: data(),
+        }
+    }
+
+    pub fn record(&mut self, x: usize, y: usize, sample: &AovSample) {
+        let normal = sample.normal;
+        let point = sample.position;
+
+        self.depth[y][x] = gray(sample.depth);
+        self.normal[y][x] = Color::new(normal[0], normal[1], normal[2]);
+        self.albedo[y][x] = sample.albedo;
+        self.object_id[y][x] = gray(sample.object_id as f64 + 1.0);
+        self.position[y][x] = Color::new(point[0], point[1], point[2]);
+    }
+}
+
+//What the AOVs store for a single hit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AovSample {
+    pub depth: f64,
+    pub normal: Vector<f64, 4>,
+    pub albedo: Color,
+    //Index of the object in World::objects.
+    pub object_id: usize,
+    pub position: Point<f64, 4>,
+}
+
+impl AovSample {
+    pub fn new(comps: &Computations, object_id: usize) -> Self {
+        Self {
+            depth: comps.t,
+            normal: comps.normalv,
+            albedo: comps.object.material.color_at(comps.object, &comps.point),
+            object_id,
+            position: comps.point,
+        }
+    }
+}
+
+fn gray(value: f64) -> Color {
+    Color::new(value, value, value)
+}
@@ -0,0 +1,115 @@
+use crate::{Matrix, Noise, Point, SurfacePoint, Vector};
+
+use super::{Height, NoiseBump, NormalMap, Ripple};
+
+//Perturbs the normal of a surface to fake small details (ripples,
+// dents, engraved patterns) without adding any geometry.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Bump {
+    Ripple(Ripple),
+    Height(Height),
+    Noise(NoiseBump),
+    NormalMap(NormalMap),
+    None,
+}
+
+impl Bump {
+    pub fn ripple_bump(amplitude: f64, frequency: f64) -> Bump {
+        Bump::Ripple(Ripple::new(amplitude, frequency))
+    }
+
+    pub fn height_bump(height: fn(&Point<f64, 4>) -> f64, scale: f64) -> Bump {
+        Bump::Height(Height::new(height, scale))
+    }
+
+    pub fn noise_bump(seed: u64, amplitude: f64, frequency: f64, octaves: u32) -> Bump {
+        Bump::Noise(NoiseBump::new(
+            Noise::new(seed),
+            amplitude,
+            frequency,
+            octaves,
+        ))
+    }
+
+    //`object_point` is the hit in object space, `normal` the already
+    // transformed world normal and `inverse_transpose` the matrix that
+    // took it there. Returns the new, normalized, world normal.
+    pub fn perturb(
+        &self,
+        object_point: &Point<f64, 4>,
+        normal: Vector<f64, 4>,
+        inverse_transpose: &Matrix<f64, 4, 4>,
+    ) -> Vector<f64, 4> {
+        self.perturb_on_surface(
+            &SurfacePoint::none(),
+            object_point,
+            normal,
+            inverse_transpose,
+        )
+    }
+
+    //perturb for a point on `surface`, which UvMapping::Surface normal maps
+    // read their uv from.
+    pub fn perturb_on_surface(
+        &self,
+        surface: &SurfacePoint,
+        object_point: &Point<f64, 4>,
+        normal: Vector<f64, 4>,
+        inverse_transpose: &Matrix<f64, 4, 4>,
+    ) -> Vector<f64, 4> {
+        match self {
+            Bump::Ripple(b) => {
+                let gradient = to_world(b.gradient_at(object_point), inverse_transpose);
+                tilt(normal, gradient)
+            }
+            Bump::Height(b) => {
+                let gradient = to_world(b.gradient_at(object_point), inverse_transpose);
+                tilt(normal, gradient)
+            }
+            Bump::Noise(b) => {
+                let gradient = to_world(b.gradient_at(object_point), inverse_transpose);
+                tilt(normal, gradient)
+            }
+            Bump::NormalMap(b) => {
+                b.normal_at_surface(surface, object_point, normal, inverse_transpose)
+            }
+            Bump::None => normal,
+        }
+    }
+}
+
+//Gradients transform like normals, through the inverse transpose.
+pub(crate) fn to_world(
+    gradient: Vector<f64, 4>,
+    inverse_transpose: &Matrix<f64, 4, 4>,
+) -> Vector<f64, 4> {
+    let mut world = *inverse_transpose * gradient;
+    world[3] = 0.0;
+    world
+}
+
+//Bends the normal against the slope of a height field: only the part
+// of the gradient that lies along the surface matters.
+fn tilt(normal: Vector<f64, 4>, gradient: Vector<f64, 4>) -> Vector<f64, 4> {
+    let tangential = gradient - normal * (gradient * normal);
+    (normal - tangential).normalize()
+}
+
+//Central differences of `f` around `point`.
+pub(crate) fn numeric_gradient<F>(point: &Point<f64, 4>, f: F) -> Vector<f64, 4>
+where
+    F: Fn(&Point<f64, 4>) -> f64,
+{
+    let h = 1.0e-4;
+    let mut gradient = Vector::new_vec3D(0.0, 0.0, 0.0);
+
+    for axis in 0..3 {
+        let mut ahead = *point;
+        let mut behind = *point;
+        ahead[axis] += h;
+        behind[axis] -= h;
+        gradient[axis] = (f(&ahead) - f(&behind)) / (2.0 * h);
+    }
+
+    gradient
+}
@@ -0,0 +1,28 @@
+use crate::{Point, Vector};
+
+use super::bump_type::numeric_gradient;
+
+//Any height function over object space, e.g. |p| (p[0] * 10.0).sin() * 0.1.
+// The normal is tilted by its numerically estimated slope times `scale`.
+#[derive(Clone, Copy, Debug)]
+pub struct Height {
+    pub height: fn(&Point<f64, 4>) -> f64,
+    pub scale: f64,
+}
+
+impl Height {
+    pub fn new(height: fn(&Point<f64, 4>) -> f64, scale: f64) -> Self {
+        Self { height, scale }
+    }
+
+    pub fn gradient_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        numeric_gradient(point, self.height) * self.scale
+    }
+}
+
+//Height functions can only be told apart by their address.
+impl PartialEq for Height {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::fn_addr_eq(self.height, other.height) && self.scale == other.scale
+    }
+}
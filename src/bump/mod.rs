@@ -0,0 +1,10 @@
+pub mod ripple;
+pub use ripple::Ripple;
+pub mod height;
+pub use height::Height;
+pub mod normal_map;
+pub use normal_map::NormalMap;
+pub mod noise_bump;
+pub use noise_bump::NoiseBump;
+pub mod bump_type;
+pub use bump_type::Bump;
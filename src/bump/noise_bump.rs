@@ -0,0 +1,35 @@
+use crate::{Noise, Point, Vector};
+
+use super::bump_type::numeric_gradient;
+
+//Bumps following fractal noise, for stucco, hammered metal or choppy water.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseBump {
+    pub noise: Noise,
+    pub amplitude: f64,
+    //number of bumps per unit of object space
+    pub frequency: f64,
+    pub octaves: u32,
+}
+
+impl NoiseBump {
+    pub fn new(noise: Noise, amplitude: f64, frequency: f64, octaves: u32) -> Self {
+        Self {
+            noise,
+            amplitude,
+            frequency,
+            octaves,
+        }
+    }
+
+    pub fn gradient_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        numeric_gradient(point, |p| {
+            let scaled = Point::new_point3D(
+                p[0] * self.frequency,
+                p[1] * self.frequency,
+                p[2] * self.frequency,
+            );
+            self.noise.fbm(&scaled, self.octaves) * self.amplitude
+        })
+    }
+}
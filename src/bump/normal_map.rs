@@ -0,0 +1,104 @@
+use crate::{ImageTexture, Matrix, Point, SurfacePoint, UvMapping, Vector};
+
+use super::bump_type::{numeric_gradient, to_world};
+
+//A tangent space normal map: each texel stores a normal with x along u,
+// y along v and z away from the surface, encoded from [-1, 1] into [0, 1].
+// Load the texture with ImageTexture::load_linear.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NormalMap {
+    pub texture: ImageTexture,
+    pub mapping: UvMapping,
+    //scales the deviation from the surface normal, 0.0 disables the map
+    pub strength: f64,
+}
+
+impl NormalMap {
+    pub fn new(texture: ImageTexture, mapping: UvMapping, strength: f64) -> Self {
+        Self {
+            texture,
+            mapping,
+            strength,
+        }
+    }
+
+    pub fn normal_at(
+        &self,
+        object_point: &Point<f64, 4>,
+        normal: Vector<f64, 4>,
+        inverse_transpose: &Matrix<f64, 4, 4>,
+    ) -> Vector<f64, 4> {
+        self.normal_at_surface(
+            &SurfacePoint::none(),
+            object_point,
+            normal,
+            inverse_transpose,
+        )
+    }
+
+    //Leaves the normal as it is when the surface mapping has no shape to
+    // read uv from.
+    pub fn normal_at_surface(
+        &self,
+        surface: &SurfacePoint,
+        object_point: &Point<f64, 4>,
+        normal: Vector<f64, 4>,
+        inverse_transpose: &Matrix<f64, 4, 4>,
+    ) -> Vector<f64, 4> {
+        let uv_at = |p: &Point<f64, 4>| match self.mapping {
+            UvMapping::Surface => surface.uv_at(p),
+            mapping => Some(mapping.map(p)),
+        };
+        let Some((u, v)) = uv_at(object_point) else {
+            return normal;
+        };
+        let texel = self.texture.uv_texture_at(u, v);
+        let x = (texel.red * 2.0 - 1.0) * self.strength;
+        let y = (texel.green * 2.0 - 1.0) * self.strength;
+        let z = texel.blue * 2.0 - 1.0;
+
+        //the same shape gives uv all around the point
+        let uv_at = |p: &Point<f64, 4>| uv_at(p).unwrap();
+        let (tangent, bitangent) =
+            self.tangent_frame(uv_at, object_point, normal, inverse_transpose);
+
+        (tangent * x + bitangent * y + normal * z).normalize()
+    }
+
+    //Directions in which u and v grow along the surface, in world space.
+    fn tangent_frame(
+        &self,
+        uv_at: impl Fn(&Point<f64, 4>) -> (f64, f64),
+        object_point: &Point<f64, 4>,
+        normal: Vector<f64, 4>,
+        inverse_transpose: &Matrix<f64, 4, 4>,
+    ) -> (Vector<f64, 4>, Vector<f64, 4>) {
+        let (u0, v0) = uv_at(object_point);
+        //u and v wrap around from 1 to 0, keep the differences small
+        let unwrap = |delta: f64| delta - delta.round();
+        let du = numeric_gradient(object_point, |p| unwrap(uv_at(p).0 - u0));
+        let dv = numeric_gradient(object_point, |p| unwrap(uv_at(p).1 - v0));
+
+        let du = to_world(du, inverse_transpose);
+        let dv = to_world(dv, inverse_transpose);
+
+        let mut tangent = du - normal * (du * normal);
+        if tangent.magnitude() < 1.0e-9 {
+            //u doesn't change here (e.g. at a pole), any direction will do
+            let axis = if normal[0].abs() < 0.9 {
+                Vector::new_vec3D(1.0, 0.0, 0.0)
+            } else {
+                Vector::new_vec3D(0.0, 0.0, 1.0)
+            };
+            tangent = axis - normal * (axis * normal);
+        }
+        let tangent = tangent.normalize();
+
+        let mut bitangent = dv - normal * (dv * normal) - tangent * (dv * tangent);
+        if bitangent.magnitude() < 1.0e-9 {
+            bitangent = tangent | normal;
+        }
+
+        (tangent, bitangent.normalize())
+    }
+}
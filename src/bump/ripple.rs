@@ -0,0 +1,31 @@
+use crate::{Point, Vector};
+
+//Concentric waves around the y axis, like a drop falling in water.
+// The height of the surface is amplitude * sin(frequency * distance).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ripple {
+    pub amplitude: f64,
+    pub frequency: f64,
+}
+
+impl Ripple {
+    pub fn new(amplitude: f64, frequency: f64) -> Self {
+        Self {
+            amplitude,
+            frequency,
+        }
+    }
+
+    pub fn gradient_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        let x = point[0];
+        let z = point[2];
+        let distance = f64::sqrt(x * x + z * z);
+
+        if distance == 0.0 {
+            return Vector::new_vec3D(0.0, 0.0, 0.0);
+        }
+
+        let slope = self.amplitude * self.frequency * f64::cos(self.frequency * distance);
+        Vector::new_vec3D(slope * x / distance, 0.0, slope * z / distance)
+    }
+}
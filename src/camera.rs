@@ -1,7 +1,14 @@
-use crate::{transformation::*, Canvas, Matrix, Motion, Point, Ray, Vector, World};
+use crate::aovs::AovSample;
+use crate::intersection::Computations;
+use crate::{
+    color::Color, transformation::*, Aovs, Canvas, Matrix, Motion, Object, Point,
+    ProgressiveRender, Ray, Vector, World,
+};
 use colored::{self, Colorize};
 use indicatif::ProgressBar;
 use rayon::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Camera {
@@ -13,6 +20,9 @@ pub struct Camera {
     pub pixel_size: f64,
     pub half_width: f64,
     pub half_height: f64,
+    //Rays per pixel along each side, samples * samples of them are
+    // spread over a regular grid and averaged.
+    pub samples: usize,
 }
 
 impl Motion for Camera {
@@ -89,31 +99,141 @@ impl Camera {
             pixel_size,
             half_width,
             half_height,
+            samples: 1,
         }
     }
 
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    //Pixels are averaged over their samples, so their colors are
+    // premultiplied by the coverage stored in the alpha channel: the
+    // fraction of samples that hit something.
     pub fn render(&self, world: &World) -> Canvas {
         let mut image = Canvas::new(self.hsize, self.vsize);
-        let pb = ProgressBar::new(image.pixels().len() as u64);
+        let count = self.samples * self.samples;
+
+        let pixels = self.trace_pixels(|px, py| {
+            let mut color = Color::black();
+            let mut hits = 0;
+            for sy in 0..self.samples {
+                for sx in 0..self.samples {
+                    let dx = (sx as f64 + 0.5) / self.samples as f64;
+                    let dy = (sy as f64 + 0.5) / self.samples as f64;
+                    if let Some(sample) = self.sample(world, px, py, dx, dy) {
+                        color = color + sample;
+                        hits += 1;
+                    }
+                }
+            }
+            (color * (1.0 / count as f64), hits as f64 / count as f64)
+        });
+
+        for (px, py, (color, coverage)) in pixels {
+            image[py][px] = color;
+            image.set_alpha(px, py, coverage);
+        }
+        image
+    }
+
+    //Same image as render, plus the depth, normal, albedo, object id and
+    // position of the first hit through every pixel. Only the center of
+    // each pixel is sampled.
+    pub fn render_with_aovs(&self, world: &World) -> (Canvas, Aovs) {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+        let mut aovs = Aovs::new(self.hsize, self.vsize);
+        //an object's id is its index in the world, found from the address of
+        // the object hit instead of searching the world for every pixel
+        let object_ids: HashMap<usize, usize> = world
+            .objects
+            .iter()
+            .enumerate()
+            .map(|(id, object)| (object as *const Object as usize, id))
+            .collect();
+
+        let samples = self.trace_pixels(|px, py| {
+            let ray = self.ray_for_pixel(px, py);
+            let inters = world.intersect_world(&ray);
+            let hit = inters.hit_index()?;
+            let comps = Computations::prepare_computation(&inters, hit, &ray);
+            let object_id = *object_ids.get(&(comps.object as *const Object as usize))?;
+
+            let color = world.shade_hit(&comps, world.reflection_limit);
+            Some((color, AovSample::new(&comps, object_id)))
+        });
+
+        for (px, py, sample) in samples {
+            match sample {
+                Some((color, sample)) => {
+                    image[py][px] = color;
+                    aovs.record(px, py, &sample);
+                }
+                None => image.set_alpha(px, py, 0.0),
+            }
+        }
+        (image, aovs)
+    }
+
+    //Renders one pass at a time, see ProgressiveRender.
+    pub fn render_progressive<'a>(&'a self, world: &'a World) -> ProgressiveRender<'a> {
+        ProgressiveRender::new(self, world)
+    }
+
+    //Runs `trace` on every pixel in parallel, showing a progress bar.
+    fn trace_pixels<T: Send>(
+        &self,
+        trace: impl Fn(usize, usize) -> T + Sync,
+    ) -> Vec<(usize, usize, T)> {
+        let pb = ProgressBar::new((self.hsize * self.vsize) as u64);
 
         println!("{}", "RayTracing...".italic().bold());
+        //without a flag nothing can cancel it
+        self.map_pixels(None, |px, py| {
+            let result = trace(px, py);
+            pb.inc(1);
+            result
+        })
+        .unwrap()
+    }
+
+    //Runs `trace` on every pixel in parallel. Pixels not yet started are
+    // skipped once `cancel` is set, and the result is then None.
+    pub(crate) fn map_pixels<T: Send>(
+        &self,
+        cancel: Option<&AtomicBool>,
+        trace: impl Fn(usize, usize) -> T + Sync,
+    ) -> Option<Vec<(usize, usize, T)>> {
         (0..self.hsize)
             .flat_map(move |px| (0..self.vsize).map(move |py| (px, py)))
             .collect::<Vec<_>>()
             .into_par_iter()
-            .map(|(px, py)| {
-                let ray = self.ray_for_pixel(px, py);
-                pb.inc(1);
-                let color = world.color_at(&ray, world.reflection_limit);
-                (px, py, color)
+            .map(|(px, py)| match cancel {
+                Some(cancel) if cancel.load(Ordering::Relaxed) => None,
+                _ => Some((px, py, trace(px, py))),
             })
-            .collect::<Vec<_>>()
-            .into_iter()
-            .for_each(|(px, py, color)| {
-                image[py][px] = color;
-            });
-        image
+            .collect()
     }
+
+    //The color seen through a point of a pixel, None when the ray hits
+    // nothing.
+    pub(crate) fn sample(
+        &self,
+        world: &World,
+        px: usize,
+        py: usize,
+        dx: f64,
+        dy: f64,
+    ) -> Option<Color> {
+        let ray = self.ray_for_subpixel(px, py, dx, dy);
+        let inters = world.intersect_world(&ray);
+        let hit = inters.hit_index()?;
+        let comps = Computations::prepare_computation(&inters, hit, &ray);
+
+        Some(world.shade_hit(&comps, world.reflection_limit))
+    }
+
     fn compute_pixel_size(hsize: usize, vsize: usize, fov: f64) -> (f64, f64, f64) {
         let half_view = f64::tan(fov / 2.0);
         let aspect = hsize as f64 / vsize as f64;
@@ -134,9 +254,15 @@ impl Camera {
     }
 
     pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
-        // the offset from the edge of the canvas to the pixel's center
-        let xoffset = (px as f64 + 0.5) * self.pixel_size;
-        let yoffset = (py as f64 + 0.5) * self.pixel_size;
+        self.ray_for_subpixel(px, py, 0.5, 0.5)
+    }
+
+    //`dx` and `dy` place the ray inside the pixel, from 0.0 (left, top)
+    // to 1.0 (right, bottom).
+    pub fn ray_for_subpixel(&self, px: usize, py: usize, dx: f64, dy: f64) -> Ray {
+        // the offset from the edge of the canvas to the point in the pixel
+        let xoffset = (px as f64 + dx) * self.pixel_size;
+        let yoffset = (py as f64 + dy) * self.pixel_size;
 
         // the untransformed coordinates of the pixel in world space.
         // (remember that the camera looks toward -z, so +x is to the *left*.)
@@ -1,24 +1,43 @@
 use super::color::Color;
+use crate::{OutputTransform, PngOptions};
 use colored::Colorize;
-use std::fs::{self, File};
+use image::ImageEncoder;
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
 use std::ops::{Index, IndexMut};
 
 pub struct Canvas {
     pub width: usize,
     pub height: usize,
+    //How colors are turned into 8 bit values for PNG and PPM files.
+    pub output_transform: OutputTransform,
+    //Colors are premultiplied by their alpha.
     pixels: Vec<Color>,
+    //Coverage of each pixel, 0.0 where nothing was rendered.
+    alpha: Vec<f64>,
 }
 
+const PPM_LINE_LENGTH: usize = 70;
+const SSIM_WINDOW: usize = 7;
+
 impl Canvas {
     pub fn new(width: usize, height: usize) -> Self {
         Self {
             width,
             height,
+            output_transform: OutputTransform::new(),
             pixels: vec![Color::black(); width * height],
+            alpha: vec![1.0; width * height],
         }
     }
 
+    pub fn with_output_transform(mut self, output_transform: OutputTransform) -> Self {
+        self.output_transform = output_transform;
+        self
+    }
+
     pub fn set_pixels(&mut self, pixels: Vec<Color>) {
+        assert_eq!(pixels.len(), self.width * self.height);
         self.pixels = pixels;
     }
 
@@ -27,79 +46,366 @@ impl Canvas {
     }
     pub fn write_pixel(&mut self, pixel: Color) {
         self.pixels.push(pixel);
+        self.alpha.push(1.0);
     }
 
-    pub fn convert_to_png(&self, file_name: &str) -> image::ImageResult<()> {
-        let mut img = image::ImageBuffer::new(self.width as u32, self.height as u32);
-        let _output = File::create(file_name);
+    pub fn alpha_at(&self, x: usize, y: usize) -> f64 {
+        self.alpha[y * self.width + x]
+    }
+
+    pub fn set_alpha(&mut self, x: usize, y: usize, alpha: f64) {
+        self.alpha[y * self.width + x] = alpha;
+    }
 
+    //Porter-Duff "over": this canvas in front of `background`, which must
+    // be the same size. Keeps this canvas' output transform.
+    pub fn over(&self, background: &Canvas) -> Canvas {
+        self.assert_same_size(background);
 
-        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let mut result =
+            Canvas::new(self.width, self.height).with_output_transform(self.output_transform);
+        for i in 0..self.pixels.len() {
+            let alpha = self.alpha[i];
+            result.pixels[i] = self.pixels[i] + background.pixels[i] * (1.0 - alpha);
+            result.alpha[i] = alpha + background.alpha[i] * (1.0 - alpha);
+        }
+        result
+    }
 
-        for (x, y, pixel) in img.enumerate_pixels_mut() {
-            let color = &self[y as usize][x as usize];
-            let r = scale_color(color.red);
-            let g = scale_color(color.green);
-            let b = scale_color(color.blue);
+    //Root mean square difference of every color channel.
+    pub fn rmse(&self, other: &Canvas) -> f64 {
+        self.assert_same_size(other);
+        let mut sum = 0.0;
+        for (a, b) in self.pixels.iter().zip(&other.pixels) {
+            for (x, y) in channels(a).into_iter().zip(channels(b)) {
+                sum += (x - y) * (x - y);
+            }
+        }
+        (sum / (self.pixels.len() * 3) as f64).sqrt()
+    }
 
-            *pixel = image::Rgb([r, g, b]);
+    //Peak signal to noise ratio in decibels, for values in [0, 1].
+    // Identical canvases give infinity.
+    pub fn psnr(&self, other: &Canvas) -> f64 {
+        let rmse = self.rmse(other);
+        if rmse == 0.0 {
+            f64::INFINITY
+        } else {
+            -20.0 * rmse.log10()
         }
+    }
 
-        img.save(file_name)
+    //The largest difference of any channel of any pixel.
+    pub fn max_difference(&self, other: &Canvas) -> f64 {
+        self.assert_same_size(other);
+        self.pixels
+            .iter()
+            .zip(&other.pixels)
+            .map(|(a, b)| pixel_difference(a, b))
+            .fold(0.0, f64::max)
+    }
+
+    //Mean structural similarity of the luminance, over every 7x7 window
+    // (or smaller, for smaller canvases). 1.0 means identical.
+    pub fn ssim(&self, other: &Canvas) -> f64 {
+        self.assert_same_size(other);
+        const C1: f64 = 0.01 * 0.01;
+        const C2: f64 = 0.03 * 0.03;
+        let size = SSIM_WINDOW.min(self.width).min(self.height);
+        if size == 0 {
+            return 1.0;
+        }
+
+        let a: Vec<f64> = self.pixels.iter().map(luminance).collect();
+        let b: Vec<f64> = other.pixels.iter().map(luminance).collect();
+        let count = (size * size) as f64;
+        let mut total = 0.0;
+        let mut windows = 0;
+        for y in 0..=self.height - size {
+            for x in 0..=self.width - size {
+                let (mut mean_a, mut mean_b) = (0.0, 0.0);
+                for j in y..y + size {
+                    for i in x..x + size {
+                        mean_a += a[j * self.width + i];
+                        mean_b += b[j * self.width + i];
+                    }
+                }
+                mean_a /= count;
+                mean_b /= count;
+
+                let (mut var_a, mut var_b, mut covariance) = (0.0, 0.0, 0.0);
+                for j in y..y + size {
+                    for i in x..x + size {
+                        let da = a[j * self.width + i] - mean_a;
+                        let db = b[j * self.width + i] - mean_b;
+                        var_a += da * da;
+                        var_b += db * db;
+                        covariance += da * db;
+                    }
+                }
+                var_a /= count;
+                var_b /= count;
+                covariance /= count;
+
+                total += ((2.0 * mean_a * mean_b + C1) * (2.0 * covariance + C2))
+                    / ((mean_a * mean_a + mean_b * mean_b + C1) * (var_a + var_b + C2));
+                windows += 1;
+            }
+        }
+        total / windows as f64
+    }
+
+    //False color picture of where the canvases differ, going from black
+    // (equal) over blue, green and yellow to red. The largest channel
+    // difference of a pixel is multiplied by `scale` first, so 1.0 shows a
+    // difference of 1.0 as red.
+    pub fn difference_image(&self, other: &Canvas, scale: f64) -> Canvas {
+        self.assert_same_size(other);
+        let mut result = Canvas::new(self.width, self.height)
+            .with_output_transform(OutputTransform::new().with_srgb(false));
+        for i in 0..self.pixels.len() {
+            result.pixels[i] = heat(pixel_difference(&self.pixels[i], &other.pixels[i]) * scale);
+        }
+        result
+    }
+
+    fn assert_same_size(&self, other: &Canvas) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+    }
+
+    //The color with its alpha divided back out, as PNG stores it.
+    fn straight_color(&self, i: usize) -> Color {
+        let alpha = self.alpha[i];
+        if alpha > 0.0 {
+            self.pixels[i] * (1.0 / alpha)
+        } else {
+            Color::black()
+        }
+    }
+
+    pub fn convert_to_png(&self, file_name: &str) -> image::ImageResult<()> {
+        self.convert_to_png_with(file_name, PngOptions::new())
+    }
+
+    pub fn convert_to_png_with(
+        &self,
+        file_name: &str,
+        options: PngOptions,
+    ) -> image::ImageResult<()> {
+        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let file = BufWriter::new(File::create(file_name)?);
+
+        self.write_png(file, options)
+    }
+
+    pub fn write_png<W: Write>(&self, sink: W, options: PngOptions) -> image::ImageResult<()> {
+        let channels = if options.alpha { 4 } else { 3 };
+        let pixel_count = self.width * self.height;
+        let encoder = image::codecs::png::PngEncoder::new(sink);
+
+        if options.sixteen_bit {
+            //the encoder wants native endian samples
+            let mut bytes = Vec::with_capacity(pixel_count * channels * 2);
+            for i in 0..pixel_count {
+                if options.alpha {
+                    let rgb = self.output_transform.to_rgb16(&self.straight_color(i));
+                    let alpha = quantize_alpha(self.alpha[i], u16::MAX as f64) as u16;
+                    for sample in rgb.iter().chain([&alpha]) {
+                        bytes.extend(sample.to_ne_bytes());
+                    }
+                } else {
+                    for sample in self.output_transform.to_rgb16(&self.pixels[i]) {
+                        bytes.extend(sample.to_ne_bytes());
+                    }
+                }
+            }
+            let color_type = if options.alpha {
+                image::ColorType::Rgba16
+            } else {
+                image::ColorType::Rgb16
+            };
+            encoder.write_image(&bytes, self.width as u32, self.height as u32, color_type)
+        } else {
+            let mut bytes = Vec::with_capacity(pixel_count * channels);
+            for i in 0..pixel_count {
+                if options.alpha {
+                    bytes.extend(self.output_transform.to_rgb8(&self.straight_color(i)));
+                    bytes.push(quantize_alpha(self.alpha[i], u8::MAX as f64) as u8);
+                } else {
+                    bytes.extend(self.output_transform.to_rgb8(&self.pixels[i]));
+                }
+            }
+            let color_type = if options.alpha {
+                image::ColorType::Rgba8
+            } else {
+                image::ColorType::Rgb8
+            };
+            encoder.write_image(&bytes, self.width as u32, self.height as u32, color_type)
+        }
     }
 
     pub fn convert_to_ppm(&self, file_name: &str) -> Result<File, std::io::Error> {
         println!("{}{}", "Writing ".italic().bold(), file_name);
-        let image = File::create(file_name);
+        let image = File::create(file_name)?;
 
-        let mut content = String::new();
+        self.write_ppm(BufWriter::new(&image))?;
+        Ok(image)
+    }
+
+    //Plain text P3, one row at a time. Lines are wrapped before they get
+    // longer than 70 characters and every row starts on a new line.
+    pub fn write_ppm<W: Write>(&self, mut sink: W) -> Result<(), std::io::Error> {
         //Header
-        content.push_str("P3\n");
-        content.push_str(self.width.to_string().as_str());
-        content.push(' ');
-        content.push_str(self.height.to_string().as_str());
-        content.push_str("\n255\n");
+        write!(sink, "P3\n{} {}\n255\n", self.width, self.height)?;
 
+        let mut line = String::new();
         for i in 0..self.height {
-            for j in 0..self.width {
-                let mut temp = color_into_pixel(&self[i][j]);
-                if j < self.width - 1 {
-                    temp.push(' ');
-                } else {
-                    temp.push('\n');
+            let mut line_length = 0;
+            for color in &self[i] {
+                for value in self.output_transform.to_rgb8(color) {
+                    let value = value.to_string();
+                    if line_length > 0 && line_length + 1 + value.len() > PPM_LINE_LENGTH {
+                        line.push('\n');
+                        line_length = 0;
+                    } else if line_length > 0 {
+                        line.push(' ');
+                        line_length += 1;
+                    }
+                    line.push_str(&value);
+                    line_length += value.len();
+                }
+            }
+            line.push('\n');
+            sink.write_all(line.as_bytes())?;
+            line.clear();
+        }
+        sink.flush()
+    }
+
+    pub fn convert_to_ppm_binary(&self, file_name: &str) -> Result<(), std::io::Error> {
+        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let file = BufWriter::new(File::create(file_name)?);
+
+        self.write_ppm_binary(file)
+    }
+
+    //Raw P6, three bytes per pixel, one row at a time.
+    pub fn write_ppm_binary<W: Write>(&self, mut sink: W) -> Result<(), std::io::Error> {
+        write!(sink, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        let mut row = Vec::with_capacity(self.width * 3);
+        for i in 0..self.height {
+            for color in &self[i] {
+                row.extend(self.output_transform.to_rgb8(color));
+            }
+            sink.write_all(&row)?;
+            row.clear();
+        }
+        sink.flush()
+    }
+
+    pub fn convert_to_pfm(&self, file_name: &str) -> Result<(), std::io::Error> {
+        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let file = BufWriter::new(File::create(file_name)?);
+
+        self.write_pfm(file)
+    }
+
+    //Portable float map, the raw colors as 32 bit floats without any
+    // clamping. Rows are stored from the bottom up.
+    pub fn write_pfm<W: Write>(&self, mut sink: W) -> Result<(), std::io::Error> {
+        //a negative scale means little endian
+        write!(sink, "PF\n{} {}\n-1.0\n", self.width, self.height)?;
+        for i in (0..self.height).rev() {
+            for color in &self[i] {
+                for channel in [color.red, color.green, color.blue] {
+                    sink.write_all(&(channel as f32).to_le_bytes())?;
                 }
-                content.push_str(temp.as_str());
             }
         }
-        fs::write(file_name, content)?;
-        image
+        sink.flush()
+    }
+
+    pub fn convert_to_hdr(&self, file_name: &str) -> image::ImageResult<()> {
+        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let file = BufWriter::new(File::create(file_name)?);
+
+        self.write_hdr(file)
+    }
+
+    //Radiance RGBE, a shared 8 bit exponent per pixel. Negative values
+    // can not be stored and become 0.
+    pub fn write_hdr<W: Write>(&self, sink: W) -> image::ImageResult<()> {
+        let pixels: Vec<image::Rgb<f32>> = self.float_image().pixels().copied().collect();
+        image::codecs::hdr::HdrEncoder::new(sink).encode(&pixels, self.width, self.height)
+    }
+
+    pub fn convert_to_exr(&self, file_name: &str) -> image::ImageResult<()> {
+        println!("{}{}", "Writing ".italic().bold(), file_name);
+        let file = BufWriter::new(File::create(file_name)?);
+
+        self.write_exr(file)
+    }
+
+    //OpenEXR with 32 bit float channels. The format needs to seek back
+    // while writing.
+    pub fn write_exr<W: Write + Seek>(&self, sink: W) -> image::ImageResult<()> {
+        let bytes: Vec<u8> = self
+            .float_image()
+            .as_raw()
+            .iter()
+            .flat_map(|sample| sample.to_ne_bytes())
+            .collect();
+
+        image::codecs::openexr::OpenExrEncoder::new(sink).write_image(
+            &bytes,
+            self.width as u32,
+            self.height as u32,
+            image::ColorType::Rgb32F,
+        )
+    }
+
+    fn float_image(&self) -> image::Rgb32FImage {
+        image::Rgb32FImage::from_fn(self.width as u32, self.height as u32, |x, y| {
+            let color = &self[y as usize][x as usize];
+            image::Rgb([color.red as f32, color.green as f32, color.blue as f32])
+        })
     }
 }
 
-fn color_into_pixel(color: &Color) -> String {
-    let mut result = String::new();
-    let red = scale_color(color.red);
-    let blue = scale_color(color.blue);
-    let green = scale_color(color.green);
-    result.push_str(red.to_string().as_str());
-    result.push(' ');
-    result.push_str(green.to_string().as_str());
-    result.push(' ');
-    result.push_str(blue.to_string().as_str());
-
-    result
+fn channels(color: &Color) -> [f64; 3] {
+    [color.red, color.green, color.blue]
 }
 
-fn scale_color(component: f64) -> u8 {
-    let component = if component < 0.0 {
-        0.0
-    } else if component > 1.0 {
-        1.0
-    } else {
-        component
-    };
+fn pixel_difference(a: &Color, b: &Color) -> f64 {
+    channels(a)
+        .into_iter()
+        .zip(channels(b))
+        .map(|(x, y)| (x - y).abs())
+        .fold(0.0, f64::max)
+}
+
+//Rec. 709 weights.
+fn luminance(color: &Color) -> f64 {
+    0.2126 * color.red + 0.7152 * color.green + 0.0722 * color.blue
+}
+
+fn heat(value: f64) -> Color {
+    let stops = [
+        Color::black(),
+        Color::new(0.0, 0.0, 1.0),
+        Color::new(0.0, 1.0, 0.0),
+        Color::new(1.0, 1.0, 0.0),
+        Color::new(1.0, 0.0, 0.0),
+    ];
+    let position = value.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+    let i = (position.floor() as usize).min(stops.len() - 2);
+    let t = position - i as f64;
+    stops[i] * (1.0 - t) + stops[i + 1] * t
+}
 
-    (component * 255.0) as u8
+fn quantize_alpha(alpha: f64, max: f64) -> f64 {
+    (alpha.clamp(0.0, 1.0) * max).round()
 }
 
 impl Index<usize> for Canvas {
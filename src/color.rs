@@ -54,6 +54,46 @@ impl Color {
             blue: 1.0,
         }
     }
+
+    pub fn channel(&self, channel: Channel) -> f64 {
+        match channel {
+            Channel::Red => self.red,
+            Channel::Green => self.green,
+            Channel::Blue => self.blue,
+        }
+    }
+
+    //A color with only `channel` set to `value`
+    pub fn from_channel(channel: Channel, value: f64) -> Color {
+        let mut color = Color::black();
+        match channel {
+            Channel::Red => color.red = value,
+            Channel::Green => color.green = value,
+            Channel::Blue => color.blue = value,
+        }
+        color
+    }
+}
+
+//The wavelength each channel stands for when light is split by dispersion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+}
+
+impl Channel {
+    pub const ALL: [Channel; 3] = [Channel::Red, Channel::Green, Channel::Blue];
+
+    //In nanometers
+    pub fn wavelength(&self) -> f64 {
+        match self {
+            Channel::Red => 650.0,
+            Channel::Green => 550.0,
+            Channel::Blue => 450.0,
+        }
+    }
 }
 
 impl PartialEq for Color {
@@ -48,6 +48,8 @@ impl<'a> Intersection<'a> {
             t: self.t,
             n1: 0.0,
             n2: 0.0,
+            n1_object: None,
+            n2_object: None,
             object: self.object,
             point: point1,
             over_point: over_point1,
@@ -59,7 +61,7 @@ impl<'a> Intersection<'a> {
         }
     }
 
-    pub fn get_object(&self) -> &Object {
+    pub fn get_object(&self) -> &'a Object {
         self.object
     }
 
@@ -151,6 +153,10 @@ pub struct Computations<'a> {
     pub t: f64,
     pub n1: f64,
     pub n2: f64,
+    //the objects whose interiors the ray is in before and after crossing
+    // the hit surface, None meaning empty space.
+    pub n1_object: Option<&'a Object>,
+    pub n2_object: Option<&'a Object>,
     pub object: &'a Object,
     pub point: Point<f64, 4>,
     pub over_point: Point<f64, 4>,
@@ -162,6 +168,8 @@ pub struct Computations<'a> {
 }
 
 impl<'a> Computations<'a> {
+    //n1_object and n2_object start out empty, prepare_computation finds
+    // them along with n1 and n2.
     pub fn new(
         t: f64,
         n1: f64,
@@ -179,6 +187,8 @@ impl<'a> Computations<'a> {
             t,
             n1,
             n2,
+            n1_object: None,
+            n2_object: None,
             object,
             point,
             over_point,
@@ -199,6 +209,8 @@ impl<'a> Computations<'a> {
         let mut containers: Vec<&Object> = Vec::new();
         let mut n1 = 0.0;
         let mut n2 = 0.0;
+        let mut n1_object = None;
+        let mut n2_object = None;
 
         for (i, inter) in intersections.into_iter().enumerate() {
             if i == intersection_index {
@@ -207,6 +219,7 @@ impl<'a> Computations<'a> {
                 } else {
                     let last = containers.last().unwrap();
                     n1 = last.material.refractive_index;
+                    n1_object = Some(*last);
                 }
             }
 
@@ -226,6 +239,7 @@ impl<'a> Computations<'a> {
                 } else {
                     let last = containers.last().unwrap();
                     n2 = last.material.refractive_index;
+                    n2_object = Some(*last);
                     break;
                 }
             }
@@ -235,10 +249,33 @@ impl<'a> Computations<'a> {
 
         comp.n1 = n1;
         comp.n2 = n2;
+        comp.n1_object = n1_object;
+        comp.n2_object = n2_object;
 
         comp
     }
 
+    //True when either side of the hit surface spreads light by wavelength.
+    pub fn is_dispersive(&self) -> bool {
+        [self.n1_object, self.n2_object]
+            .iter()
+            .flatten()
+            .any(|object| object.material.abbe_number > 0.0)
+    }
+
+    //n1 and n2 for a single wavelength, given in nanometers.
+    pub fn n1_at(&self, wavelength: f64) -> f64 {
+        self.n1_object.map_or(1.0, |object| {
+            object.material.refractive_index_at(wavelength)
+        })
+    }
+
+    pub fn n2_at(&self, wavelength: f64) -> f64 {
+        self.n2_object.map_or(1.0, |object| {
+            object.material.refractive_index_at(wavelength)
+        })
+    }
+
     pub fn schlick(&self) -> f64 {
         let mut cos = self.eyev * self.normalv;
 
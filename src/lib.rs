@@ -10,10 +10,25 @@ pub use utils::One;
 pub use utils::Zero;
 pub mod canvas;
 pub use canvas::Canvas;
+pub mod output_transform;
+pub use output_transform::OutputTransform;
+pub use output_transform::ToneMap;
+pub mod png_options;
+pub use png_options::PngOptions;
 pub mod color;
 pub mod shapes;
 pub use shapes::Sphere;
+pub use shapes::Disk;
+pub use shapes::Rectangle;
+pub use shapes::Annulus;
+pub use shapes::Torus;
+pub use shapes::Sdf;
+pub use shapes::Heightfield;
+pub use shapes::Mesh;
+pub use shapes::BezierPatch;
 pub use shapes::Shape;
+pub use shapes::LocalShape;
+pub use shapes::Bounds;
 pub mod ray;
 pub use ray::Ray;
 pub mod intersection;
@@ -29,14 +44,33 @@ pub mod world;
 pub use world::World;
 pub mod camera;
 pub use camera::Camera;
+pub mod aovs;
+pub use aovs::Aovs;
+pub mod progressive_render;
+pub use progressive_render::ProgressiveRender;
 pub mod pattern;
 pub use pattern::Striped;
 pub use pattern::Checker;
 pub use pattern::Gradient;
 pub use pattern::Ring;
 pub use pattern::PatternType;
+pub use pattern::TextureMap;
+pub use pattern::CubeMap;
+pub use pattern::UvMapping;
+pub use pattern::SurfacePoint;
+pub use pattern::UvPattern;
+pub use pattern::ImageTexture;
+pub use pattern::Perturbed;
+pub use pattern::Slot;
+pub use pattern::LocalPattern;
+pub use pattern::CustomPattern;
 pub use pattern::Pattern;
+pub mod noise;
+pub use noise::Noise;
+pub mod bump;
+pub use bump::Bump;
 pub mod mtx;
 pub use mtx::Motion;
 pub use mtx::Matrix;
 pub use mtx::transformation;
+pub mod loaders;
@@ -0,0 +1,47 @@
+use std::fmt;
+
+//What can go wrong when reading an image into a Canvas.
+#[derive(Debug)]
+pub enum CanvasError {
+    Io(std::io::Error),
+    Image(image::ImageError),
+    Format(String),
+}
+
+impl CanvasError {
+    pub(crate) fn format(message: impl Into<String>) -> Self {
+        CanvasError::Format(message.into())
+    }
+}
+
+impl fmt::Display for CanvasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanvasError::Io(error) => write!(f, "could not read the image: {}", error),
+            CanvasError::Image(error) => write!(f, "could not decode the image: {}", error),
+            CanvasError::Format(message) => write!(f, "invalid image file: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for CanvasError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CanvasError::Io(error) => Some(error),
+            CanvasError::Image(error) => Some(error),
+            CanvasError::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CanvasError {
+    fn from(error: std::io::Error) -> Self {
+        CanvasError::Io(error)
+    }
+}
+
+impl From<image::ImageError> for CanvasError {
+    fn from(error: image::ImageError) -> Self {
+        CanvasError::Image(error)
+    }
+}
@@ -0,0 +1,820 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::color::Color;
+use crate::pattern::image_texture::{srgb_to_linear, Filter, Wrap};
+use crate::shapes::Mesh;
+use crate::transformation::*;
+use crate::{
+    Camera, ImageTexture, Light, Material, Matrix, Object, Pattern, PatternType, Point, UvMapping,
+    Vector,
+};
+
+#[derive(Debug)]
+pub enum GltfError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    Image(image::ImageError),
+    Format(String),
+}
+
+impl GltfError {
+    fn format(message: impl Into<String>) -> Self {
+        GltfError::Format(message.into())
+    }
+}
+
+impl fmt::Display for GltfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GltfError::Io(error) => write!(f, "could not read the glTF file: {}", error),
+            GltfError::Json(error) => write!(f, "invalid glTF json: {}", error),
+            GltfError::Image(error) => write!(f, "could not decode a glTF image: {}", error),
+            GltfError::Format(message) => write!(f, "invalid glTF file: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for GltfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GltfError::Io(error) => Some(error),
+            GltfError::Json(error) => Some(error),
+            GltfError::Image(error) => Some(error),
+            GltfError::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GltfError {
+    fn from(error: std::io::Error) -> Self {
+        GltfError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for GltfError {
+    fn from(error: serde_json::Error) -> Self {
+        GltfError::Json(error)
+    }
+}
+
+impl From<image::ImageError> for GltfError {
+    fn from(error: image::ImageError) -> Self {
+        GltfError::Image(error)
+    }
+}
+
+//A perspective camera of the scene. glTF leaves the image size to the
+// renderer, so the Camera itself is made once that is known.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GltfCamera {
+    //Vertical field of view, in radians.
+    pub yfov: f64,
+    pub aspect_ratio: Option<f64>,
+    //Where the camera node sits in the world.
+    pub transformation: Matrix<f64, 4, 4>,
+}
+
+impl GltfCamera {
+    pub fn camera(&self, hsize: usize, vsize: usize) -> Camera {
+        //Camera::new wants the field of view of the longer side
+        let aspect = hsize as f64 / vsize as f64;
+        let fov = if aspect >= 1.0 {
+            2.0 * f64::atan(f64::tan(self.yfov / 2.0) * aspect)
+        } else {
+            self.yfov
+        };
+
+        //glTF cameras have +x to the right, Camera has it to the left
+        let view = scaling(-1.0, 1.0, 1.0) * self.transformation.inverse();
+        let mut camera = Camera::new(hsize, vsize, fov);
+        camera.set_transformation(&view);
+        camera
+    }
+}
+
+//Everything found in the default scene of a glTF file, already placed in
+// world space.
+#[derive(Clone, Debug, Default)]
+pub struct GltfScene {
+    pub objects: Vec<Object>,
+    pub lights: Vec<Light>,
+    pub cameras: Vec<GltfCamera>,
+}
+
+//Directional lights have no position, they are put this far away
+// against their direction instead.
+const DIRECTIONAL_LIGHT_DISTANCE: f64 = 1000.0;
+
+const GLB_MAGIC: &[u8] = b"glTF";
+const GLB_JSON_CHUNK: u32 = 0x4E4F_534A;
+const GLB_BIN_CHUNK: u32 = 0x004E_4942;
+
+const SUPPORTED_EXTENSIONS: [&str; 1] = ["KHR_lights_punctual"];
+
+//Accessors without a buffer view are all zeros and take no room in the
+// file, so their size is limited to keep a bad count from exhausting memory.
+const MAX_ZERO_ACCESSOR_VALUES: usize = 1 << 24;
+
+//Loads a .gltf file (buffers and images next to it or embedded as base64
+// data uris) or a binary .glb file.
+pub fn load_gltf<P: AsRef<Path>>(path: P) -> Result<GltfScene, GltfError> {
+    let path = path.as_ref();
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    parse_gltf(&fs::read(path)?, base_dir)
+}
+
+//`base_dir` is where relative uris are looked up. Each mesh primitive
+// becomes its own Object with the node's world transformation, point and
+// spot lights become Lights at the node's position. glTF light intensities
+// are physical units while Light has no falloff, so they are only used as
+// a multiplier of the light color.
+pub fn parse_gltf(bytes: &[u8], base_dir: &Path) -> Result<GltfScene, GltfError> {
+    let (json, binary) = if bytes.starts_with(GLB_MAGIC) {
+        split_glb(bytes)?
+    } else {
+        (bytes, None)
+    };
+    let json: Value = serde_json::from_slice(json)?;
+
+    let version = json["asset"]["version"].as_str().unwrap_or("");
+    if !version.starts_with("2.") {
+        return Err(GltfError::format(format!(
+            "unsupported glTF version '{}'",
+            version
+        )));
+    }
+    for extension in json["extensionsRequired"].as_array().into_iter().flatten() {
+        let name = extension.as_str().unwrap_or("");
+        if !SUPPORTED_EXTENSIONS.contains(&name) {
+            return Err(GltfError::format(format!(
+                "required extension {} is not supported",
+                name
+            )));
+        }
+    }
+
+    let mut document = Document {
+        json: &json,
+        base_dir,
+        buffers: load_buffers(&json, base_dir, binary)?,
+        materials: HashMap::new(),
+    };
+
+    let mut scene = GltfScene::default();
+    for root in document.roots() {
+        document.visit(root, Matrix::iden(), 0, &mut scene)?;
+    }
+    Ok(scene)
+}
+
+//A .glb file is a header followed by a json chunk and an optional binary
+// chunk holding the first buffer.
+fn split_glb(bytes: &[u8]) -> Result<(&[u8], Option<&[u8]>), GltfError> {
+    let u32_at = |offset: usize| {
+        bytes
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| GltfError::format("the glb file is truncated"))
+    };
+
+    let version = u32_at(4)?;
+    if version != 2 {
+        return Err(GltfError::format(format!(
+            "unsupported glb version {}",
+            version
+        )));
+    }
+    let length = (u32_at(8)? as usize).min(bytes.len());
+
+    let mut json = None;
+    let mut binary = None;
+    let mut offset = 12;
+    while offset + 8 <= length {
+        let chunk_length = u32_at(offset)? as usize;
+        let kind = u32_at(offset + 4)?;
+        let data = bytes
+            .get(offset + 8..offset + 8 + chunk_length)
+            .ok_or_else(|| GltfError::format("the glb file is truncated"))?;
+        match kind {
+            GLB_JSON_CHUNK if json.is_none() => json = Some(data),
+            GLB_BIN_CHUNK if binary.is_none() => binary = Some(data),
+            _ => {}
+        }
+        offset += 8 + chunk_length;
+    }
+
+    let json = json.ok_or_else(|| GltfError::format("the glb file has no json chunk"))?;
+    Ok((json, binary))
+}
+
+fn load_buffers(
+    json: &Value,
+    base_dir: &Path,
+    binary: Option<&[u8]>,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for (i, buffer) in json["buffers"].as_array().into_iter().flatten().enumerate() {
+        let data = match buffer["uri"].as_str() {
+            Some(uri) => read_uri(uri, base_dir)?,
+            //only the first buffer of a glb may live in its binary chunk
+            None => match binary {
+                Some(binary) if i == 0 => binary.to_vec(),
+                _ => return Err(GltfError::format(format!("buffer {} has no data", i))),
+            },
+        };
+
+        let length = buffer["byteLength"].as_u64().unwrap_or(0) as usize;
+        if data.len() < length {
+            return Err(GltfError::format(format!(
+                "buffer {} is shorter than its byteLength",
+                i
+            )));
+        }
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+//Relative paths and base64 data uris only, nothing is fetched remotely.
+fn read_uri(uri: &str, base_dir: &Path) -> Result<Vec<u8>, GltfError> {
+    if let Some(data) = uri.strip_prefix("data:") {
+        let (_, encoded) = data
+            .split_once(";base64,")
+            .ok_or_else(|| GltfError::format("only base64 data uris are supported"))?;
+        return decode_base64(encoded);
+    }
+    if uri.contains("://") {
+        return Err(GltfError::format(format!("{} is not a local file", uri)));
+    }
+
+    Ok(fs::read(base_dir.join(decode_percent(uri)))?)
+}
+
+fn decode_base64(encoded: &str) -> Result<Vec<u8>, GltfError> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut count = 0;
+    for c in encoded.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => return Err(GltfError::format("invalid base64 data")),
+        };
+        bits = (bits << 6) | value as u32;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            bytes.push((bits >> count) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+//Uris may escape characters such as spaces as %20.
+fn decode_percent(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn index(value: &Value) -> Option<usize> {
+    value.as_u64().map(|i| i as usize)
+}
+
+fn numbers<const N: usize>(value: &Value, default: [f64; N]) -> [f64; N] {
+    let mut output = default;
+    if let Some(array) = value.as_array().filter(|array| array.len() == N) {
+        for (o, v) in output.iter_mut().zip(array) {
+            *o = v.as_f64().unwrap_or(*o);
+        }
+    }
+    output
+}
+
+//The node's matrix, or its translation * rotation * scale.
+fn local_transformation(node: &Value) -> Matrix<f64, 4, 4> {
+    if node["matrix"].as_array().is_some() {
+        //stored column by column
+        let m = numbers(&node["matrix"], [0.0; 16]);
+        return Matrix::from([
+            [m[0], m[4], m[8], m[12]],
+            [m[1], m[5], m[9], m[13]],
+            [m[2], m[6], m[10], m[14]],
+            [m[3], m[7], m[11], m[15]],
+        ]);
+    }
+
+    let [tx, ty, tz] = numbers(&node["translation"], [0.0; 3]);
+    let [x, y, z, w] = numbers(&node["rotation"], [0.0, 0.0, 0.0, 1.0]);
+    let [sx, sy, sz] = numbers(&node["scale"], [1.0; 3]);
+    let rotation = Matrix::from([
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - z * w),
+            2.0 * (x * z + y * w),
+            0.0,
+        ],
+        [
+            2.0 * (x * y + z * w),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - x * w),
+            0.0,
+        ],
+        [
+            2.0 * (x * z - y * w),
+            2.0 * (y * z + x * w),
+            1.0 - 2.0 * (x * x + y * y),
+            0.0,
+        ],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+
+    translation(tx, ty, tz) * rotation * scaling(sx, sy, sz)
+}
+
+//Turns the index list of a primitive into triangles, following its
+// topology. Points and lines have no surface and give none.
+fn assemble_triangles(mode: u64, indices: &[usize]) -> Vec<[usize; 3]> {
+    match mode {
+        4 => indices
+            .chunks_exact(3)
+            .map(|t| [t[0], t[1], t[2]])
+            .collect(),
+        //every other strip triangle is flipped to keep the winding
+        5 => (0..indices.len().saturating_sub(2))
+            .map(|k| [indices[k], indices[k + 1 + k % 2], indices[k + 2 - k % 2]])
+            .collect(),
+        6 => (2..indices.len())
+            .map(|k| [indices[0], indices[k - 1], indices[k]])
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+//Approximates a metallic-roughness material with the Phong model: rough
+// surfaces stay diffuse, smooth metals turn into mirrors and the highlight
+// tightens as the roughness goes down.
+fn pbr_material(color: Color, metallic: f64, roughness: f64) -> Material {
+    let reflective = metallic * (1.0 - roughness);
+    let alpha = (roughness * roughness).max(0.01);
+
+    Material::new(
+        0.1,
+        color,
+        0.9 * (1.0 - reflective),
+        1.0 - roughness,
+        (2.0 / (alpha * alpha) - 2.0).max(1.0),
+        reflective,
+        0.0,
+        1.0,
+        Pattern::with_type(PatternType::None),
+    )
+}
+
+struct Document<'a> {
+    json: &'a Value,
+    base_dir: &'a Path,
+    buffers: Vec<Vec<u8>>,
+    //materials are built once so their textures are shared
+    materials: HashMap<usize, (Material, usize)>,
+}
+
+impl<'a> Document<'a> {
+    //The root nodes of the default scene. Files without scenes show every
+    // node that is not the child of another.
+    fn roots(&self) -> Vec<usize> {
+        let scene = index(&self.json["scene"]).unwrap_or(0);
+        if let Some(nodes) = self.json["scenes"][scene]["nodes"].as_array() {
+            return nodes.iter().filter_map(index).collect();
+        }
+
+        let nodes = self.json["nodes"].as_array().map_or(0, |nodes| nodes.len());
+        let children: HashSet<usize> = (0..nodes)
+            .flat_map(|i| self.json["nodes"][i]["children"].as_array())
+            .flatten()
+            .filter_map(index)
+            .collect();
+        (0..nodes).filter(|i| !children.contains(i)).collect()
+    }
+
+    fn visit(
+        &mut self,
+        node_index: usize,
+        parent: Matrix<f64, 4, 4>,
+        depth: usize,
+        scene: &mut GltfScene,
+    ) -> Result<(), GltfError> {
+        let json = self.json;
+        let node = &json["nodes"][node_index];
+        if node.is_null() {
+            return Err(GltfError::format(format!("missing node {}", node_index)));
+        }
+        if depth > json["nodes"].as_array().map_or(0, |nodes| nodes.len()) {
+            return Err(GltfError::format("the node hierarchy has a cycle"));
+        }
+
+        let transformation = parent * local_transformation(node);
+
+        if let Some(mesh) = index(&node["mesh"]) {
+            let primitives = json["meshes"][mesh]["primitives"]
+                .as_array()
+                .ok_or_else(|| GltfError::format(format!("missing mesh {}", mesh)))?;
+            for primitive in primitives {
+                if let Some(object) = self.primitive(primitive, transformation)? {
+                    scene.objects.push(object);
+                }
+            }
+        }
+        if let Some(camera) = index(&node["camera"]) {
+            if let Some(camera) = self.camera(camera, transformation)? {
+                scene.cameras.push(camera);
+            }
+        }
+        if let Some(light) = index(&node["extensions"]["KHR_lights_punctual"]["light"]) {
+            scene.lights.push(self.light(light, transformation)?);
+        }
+
+        for child in node["children"].as_array().into_iter().flatten() {
+            let child = index(child).ok_or_else(|| GltfError::format("invalid child node"))?;
+            self.visit(child, transformation, depth + 1, scene)?;
+        }
+        Ok(())
+    }
+
+    fn buffer_view(&self, view_index: usize) -> Result<&[u8], GltfError> {
+        let view = &self.json["bufferViews"][view_index];
+        let missing = || GltfError::format(format!("invalid buffer view {}", view_index));
+
+        let buffer = index(&view["buffer"])
+            .and_then(|buffer| self.buffers.get(buffer))
+            .ok_or_else(missing)?;
+        let offset = index(&view["byteOffset"]).unwrap_or(0);
+        let length = index(&view["byteLength"]).ok_or_else(missing)?;
+        let end = offset.checked_add(length).ok_or_else(missing)?;
+        buffer.get(offset..end).ok_or_else(missing)
+    }
+
+    //The elements of an accessor, `components` numbers each, one after the
+    // other. Normalized integers are mapped to [0, 1] or [-1, 1].
+    fn accessor(&self, accessor_index: usize, components: usize) -> Result<Vec<f64>, GltfError> {
+        let accessor = &self.json["accessors"][accessor_index];
+        let invalid =
+            |message: &str| GltfError::format(format!("accessor {} {}", accessor_index, message));
+
+        if accessor.is_null() {
+            return Err(invalid("is missing"));
+        }
+        if !accessor["sparse"].is_null() {
+            return Err(invalid("is sparse, which is not supported"));
+        }
+        let found = match accessor["type"].as_str() {
+            Some("SCALAR") => 1,
+            Some("VEC2") => 2,
+            Some("VEC3") => 3,
+            Some("VEC4") => 4,
+            _ => 0,
+        };
+        if found != components {
+            return Err(invalid("has an unexpected type"));
+        }
+        let component_type = accessor["componentType"].as_u64().unwrap_or(0);
+        let size = match component_type {
+            5120 | 5121 => 1,
+            5122 | 5123 => 2,
+            5125 | 5126 => 4,
+            _ => return Err(invalid("has an unknown component type")),
+        };
+        let normalized = accessor["normalized"].as_bool().unwrap_or(false);
+        let count = index(&accessor["count"]).ok_or_else(|| invalid("has no count"))?;
+        let value_count = count
+            .checked_mul(components)
+            .ok_or_else(|| invalid("is too large"))?;
+
+        //without a buffer view every value is zero
+        let Some(view_index) = index(&accessor["bufferView"]) else {
+            if value_count > MAX_ZERO_ACCESSOR_VALUES {
+                return Err(invalid("is too large"));
+            }
+            return Ok(vec![0.0; value_count]);
+        };
+        let data = self.buffer_view(view_index)?;
+        let offset = index(&accessor["byteOffset"]).unwrap_or(0);
+        let stride = index(&self.json["bufferViews"][view_index]["byteStride"])
+            .filter(|stride| *stride > 0)
+            .unwrap_or(components * size);
+
+        //the last element has to end inside the view
+        if count > 0 {
+            let end = (count - 1)
+                .checked_mul(stride)
+                .and_then(|last| last.checked_add(offset))
+                .and_then(|last| last.checked_add(components * size));
+            if end.is_none_or(|end| end > data.len()) {
+                return Err(invalid("reads past its buffer view"));
+            }
+        }
+
+        let mut values = Vec::with_capacity(value_count);
+        for element in 0..count {
+            for component in 0..components {
+                let start = offset + element * stride + component * size;
+                let bytes = data
+                    .get(start..start + size)
+                    .ok_or_else(|| invalid("reads past its buffer view"))?;
+                values.push(read_component(component_type, bytes, normalized));
+            }
+        }
+        Ok(values)
+    }
+
+    fn primitive(
+        &mut self,
+        primitive: &Value,
+        transformation: Matrix<f64, 4, 4>,
+    ) -> Result<Option<Object>, GltfError> {
+        let attributes = &primitive["attributes"];
+        let position = index(&attributes["POSITION"])
+            .ok_or_else(|| GltfError::format("a mesh primitive has no POSITION"))?;
+        let positions: Vec<Point<f64, 4>> = self
+            .accessor(position, 3)?
+            .chunks_exact(3)
+            .map(|p| Point::new_point3D(p[0], p[1], p[2]))
+            .collect();
+        let vertex_count = positions.len();
+
+        let indices: Vec<usize> = match index(&primitive["indices"]) {
+            Some(indices) => self
+                .accessor(indices, 1)?
+                .into_iter()
+                .map(|i| i as usize)
+                .collect(),
+            None => (0..vertex_count).collect(),
+        };
+        if indices.iter().any(|i| *i >= vertex_count) {
+            return Err(GltfError::format(
+                "a mesh primitive indexes a missing vertex",
+            ));
+        }
+        let mode = primitive["mode"].as_u64().unwrap_or(4);
+        let triangles = assemble_triangles(mode, &indices);
+        if triangles.is_empty() {
+            return Ok(None);
+        }
+
+        let (material, tex_coord) = match index(&primitive["material"]) {
+            Some(material) => self.material(material)?,
+            None => (pbr_material(Color::white(), 1.0, 1.0), 0),
+        };
+
+        let same_count = |values: &Vec<f64>, components: usize| {
+            if values.len() == vertex_count * components {
+                Ok(())
+            } else {
+                Err(GltfError::format(
+                    "a mesh primitive has attributes of different lengths",
+                ))
+            }
+        };
+
+        let mut mesh = Mesh::new(positions, triangles);
+        match index(&attributes["NORMAL"]) {
+            Some(normal) => {
+                let values = self.accessor(normal, 3)?;
+                same_count(&values, 3)?;
+                let normals = values
+                    .chunks_exact(3)
+                    .map(|n| Vector::new_vec3D(n[0], n[1], n[2]).normalize())
+                    .collect();
+                mesh = mesh.with_normals(normals);
+            }
+            None => mesh = mesh.with_computed_normals(),
+        }
+        if let Some(uv) = index(&attributes[format!("TEXCOORD_{}", tex_coord).as_str()]) {
+            let values = self.accessor(uv, 2)?;
+            same_count(&values, 2)?;
+            //glTF puts v = 0 at the top of the image, ImageTexture at the bottom
+            let uvs = values.chunks_exact(2).map(|t| (t[0], 1.0 - t[1])).collect();
+            mesh = mesh.with_uvs(uvs);
+        }
+        if let Some(color) = index(&attributes["COLOR_0"]) {
+            let components = match self.json["accessors"][color]["type"].as_str() {
+                Some("VEC4") => 4,
+                _ => 3,
+            };
+            let values = self.accessor(color, components)?;
+            same_count(&values, components)?;
+            let colors = values
+                .chunks_exact(components)
+                .map(|c| Color::new(c[0], c[1], c[2]))
+                .collect();
+            mesh = mesh.with_colors(colors);
+        }
+
+        let mut object = Object::new_mesh(mesh).with_transformation(transformation);
+        object.set_material(material);
+        Ok(Some(object))
+    }
+
+    //The material and the set of texture coordinates its texture uses.
+    fn material(&mut self, material_index: usize) -> Result<(Material, usize), GltfError> {
+        if let Some(material) = self.materials.get(&material_index) {
+            return Ok(material.clone());
+        }
+
+        let json = self.json;
+        let material = &json["materials"][material_index];
+        if material.is_null() {
+            return Err(GltfError::format(format!(
+                "missing material {}",
+                material_index
+            )));
+        }
+        let pbr = &material["pbrMetallicRoughness"];
+        let [red, green, blue, alpha] = numbers(&pbr["baseColorFactor"], [1.0; 4]);
+        let base_color = Color::new(red, green, blue);
+        let metallic = pbr["metallicFactor"].as_f64().unwrap_or(1.0);
+        let roughness = pbr["roughnessFactor"].as_f64().unwrap_or(1.0);
+
+        let mut result = pbr_material(base_color, metallic, roughness);
+        if material["alphaMode"].as_str() == Some("BLEND") {
+            result.transparency = 1.0 - alpha;
+        }
+
+        let texture = &pbr["baseColorTexture"];
+        let tex_coord = index(&texture["texCoord"]).unwrap_or(0);
+        if let Some(texture) = index(&texture["index"]) {
+            let texture = self.texture(texture, base_color)?;
+            result.pattern =
+                Pattern::with_type(PatternType::image_pattern(texture, UvMapping::Surface));
+        }
+
+        self.materials
+            .insert(material_index, (result.clone(), tex_coord));
+        Ok((result, tex_coord))
+    }
+
+    //The base color factor multiplies every texel.
+    fn texture(&self, texture_index: usize, factor: Color) -> Result<ImageTexture, GltfError> {
+        let texture = &self.json["textures"][texture_index];
+        let source = index(&texture["source"])
+            .ok_or_else(|| GltfError::format(format!("texture {} has no image", texture_index)))?;
+        let image = &self.json["images"][source];
+
+        let bytes = if let Some(uri) = image["uri"].as_str() {
+            read_uri(uri, self.base_dir)?
+        } else if let Some(view) = index(&image["bufferView"]) {
+            self.buffer_view(view)?.to_vec()
+        } else {
+            return Err(GltfError::format(format!("image {} has no data", source)));
+        };
+        let rgb = image::load_from_memory(&bytes)?.to_rgb32f();
+        if rgb.width() == 0 || rgb.height() == 0 {
+            return Err(GltfError::format(format!("image {} is empty", source)));
+        }
+        let texels = rgb
+            .pixels()
+            .map(|p| {
+                Color::new(
+                    srgb_to_linear(p[0] as f64),
+                    srgb_to_linear(p[1] as f64),
+                    srgb_to_linear(p[2] as f64),
+                ) * factor
+            })
+            .collect();
+
+        let sampler = match index(&texture["sampler"]) {
+            Some(sampler) => &self.json["samplers"][sampler],
+            None => &Value::Null,
+        };
+        let filter = match sampler["magFilter"].as_u64() {
+            Some(9728) => Filter::Nearest,
+            _ => Filter::Bilinear,
+        };
+        let wrap = match sampler["wrapS"].as_u64() {
+            Some(33071) => Wrap::Clamp,
+            Some(33648) => Wrap::Mirror,
+            _ => Wrap::Repeat,
+        };
+
+        Ok(
+            ImageTexture::from_colors(rgb.width() as usize, rgb.height() as usize, texels)
+                .with_filter(filter)
+                .with_wrap(wrap),
+        )
+    }
+
+    //Orthographic cameras are skipped, Camera only does perspective.
+    fn camera(
+        &self,
+        camera_index: usize,
+        transformation: Matrix<f64, 4, 4>,
+    ) -> Result<Option<GltfCamera>, GltfError> {
+        let camera = &self.json["cameras"][camera_index];
+        let invalid = || GltfError::format(format!("invalid camera {}", camera_index));
+
+        match camera["type"].as_str() {
+            Some("perspective") => Ok(Some(GltfCamera {
+                yfov: camera["perspective"]["yfov"].as_f64().ok_or_else(invalid)?,
+                aspect_ratio: camera["perspective"]["aspectRatio"].as_f64(),
+                transformation,
+            })),
+            Some("orthographic") => Ok(None),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn light(
+        &self,
+        light_index: usize,
+        transformation: Matrix<f64, 4, 4>,
+    ) -> Result<Light, GltfError> {
+        let light = &self.json["extensions"]["KHR_lights_punctual"]["lights"][light_index];
+        if light.is_null() {
+            return Err(GltfError::format(format!("missing light {}", light_index)));
+        }
+        let [red, green, blue] = numbers(&light["color"], [1.0; 3]);
+        let intensity = light["intensity"].as_f64().unwrap_or(1.0);
+
+        let origin = transformation * Point::new_point3D(0.0, 0.0, 0.0);
+        let position = match light["type"].as_str() {
+            //lights shine down their -z axis
+            Some("directional") => {
+                let backwards = transformation * Vector::new_vec3D(0.0, 0.0, 1.0);
+                origin + backwards.normalize() * DIRECTIONAL_LIGHT_DISTANCE
+            }
+            Some("point") | Some("spot") => origin,
+            _ => return Err(GltfError::format(format!("invalid light {}", light_index))),
+        };
+
+        Ok(Light::new(
+            Color::new(red, green, blue) * intensity,
+            position,
+        ))
+    }
+}
+
+fn read_component(component_type: u64, bytes: &[u8], normalized: bool) -> f64 {
+    match component_type {
+        5120 => {
+            let value = bytes[0] as i8 as f64;
+            if normalized {
+                (value / 127.0).max(-1.0)
+            } else {
+                value
+            }
+        }
+        5121 => {
+            let value = bytes[0] as f64;
+            if normalized {
+                value / 255.0
+            } else {
+                value
+            }
+        }
+        5122 => {
+            let value = i16::from_le_bytes([bytes[0], bytes[1]]) as f64;
+            if normalized {
+                (value / 32767.0).max(-1.0)
+            } else {
+                value
+            }
+        }
+        5123 => {
+            let value = u16::from_le_bytes([bytes[0], bytes[1]]) as f64;
+            if normalized {
+                value / 65535.0
+            } else {
+                value
+            }
+        }
+        5125 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+        _ => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64,
+    }
+}
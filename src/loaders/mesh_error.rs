@@ -0,0 +1,38 @@
+use std::fmt;
+
+//What can go wrong when loading a mesh file.
+#[derive(Debug)]
+pub enum MeshError {
+    Io(std::io::Error),
+    Format(String),
+}
+
+impl MeshError {
+    pub(crate) fn format(message: impl Into<String>) -> Self {
+        MeshError::Format(message.into())
+    }
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::Io(error) => write!(f, "could not read the mesh: {}", error),
+            MeshError::Format(message) => write!(f, "invalid mesh file: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MeshError::Io(error) => Some(error),
+            MeshError::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MeshError {
+    fn from(error: std::io::Error) -> Self {
+        MeshError::Io(error)
+    }
+}
@@ -0,0 +1,20 @@
+pub mod patch;
+pub use patch::load_patches;
+pub use patch::PatchError;
+pub mod mesh_error;
+pub use mesh_error::MeshError;
+pub mod ply;
+pub use ply::load_ply;
+pub mod stl;
+pub use stl::load_stl;
+pub mod gltf;
+pub use gltf::load_gltf;
+pub use gltf::GltfCamera;
+pub use gltf::GltfError;
+pub use gltf::GltfScene;
+pub mod canvas_error;
+pub use canvas_error::CanvasError;
+pub mod ppm;
+pub use ppm::load_ppm;
+pub mod png;
+pub use png::load_png;
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use crate::shapes::BezierPatch;
+use crate::Point;
+
+#[derive(Debug)]
+pub enum PatchError {
+    Io(std::io::Error),
+    //`line` starts at 1.
+    Parse { line: usize, message: String },
+    UnexpectedEnd,
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatchError::Io(error) => write!(f, "could not read the patch file: {}", error),
+            PatchError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            PatchError::UnexpectedEnd => write!(f, "the patch file ended too early"),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchError::Io(error) => Some(error),
+            PatchError::Parse { .. } | PatchError::UnexpectedEnd => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for PatchError {
+    fn from(error: std::io::Error) -> Self {
+        PatchError::Io(error)
+    }
+}
+
+pub fn load_patches<P: AsRef<Path>>(path: P) -> Result<Vec<BezierPatch>, PatchError> {
+    parse_patches(&fs::read_to_string(path)?)
+}
+
+//Reads the two classic teapot layouts, told apart by their second line:
+//
+// indexed (Newell's data): the patch count, one line of 16 vertex indices
+// (starting at 1) per patch, the vertex count and one `x, y, z` line per
+// vertex.
+//
+// .bpt: the patch count, then per patch its degrees `3 3` followed by its
+// 16 control points, one `x y z` line each.
+//
+//Numbers can be separated by commas and/or whitespace.
+pub fn parse_patches(source: &str) -> Result<Vec<BezierPatch>, PatchError> {
+    let mut lines = source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let patch_count = next_numbers(&mut lines)?.as_count()?;
+    let mut first = next_numbers(&mut lines)?;
+
+    if first.values.len() == 2 {
+        let mut patches = Vec::new();
+        for patch in 0..patch_count {
+            if patch > 0 {
+                first = next_numbers(&mut lines)?;
+            }
+            if first.values != [3.0, 3.0] {
+                return Err(first.error("only bicubic (3 3) patches are supported"));
+            }
+
+            let mut control_points = [Point::new_point3D(0.0, 0.0, 0.0); 16];
+            for point in control_points.iter_mut() {
+                *point = next_numbers(&mut lines)?.as_point()?;
+            }
+            patches.push(BezierPatch::new(control_points));
+        }
+
+        return Ok(patches);
+    }
+
+    let mut indices = Vec::new();
+    for patch in 0..patch_count {
+        let numbers = if patch == 0 {
+            first.clone()
+        } else {
+            next_numbers(&mut lines)?
+        };
+        if numbers.values.len() != 16 {
+            return Err(numbers.error("a patch needs 16 vertex indices"));
+        }
+        let line = numbers.line;
+        let patch_indices = numbers
+            .values
+            .iter()
+            .map(|i| Numbers::count(*i, line))
+            .collect::<Result<Vec<usize>, PatchError>>()?;
+        indices.push((line, patch_indices));
+    }
+
+    let vertex_count = next_numbers(&mut lines)?.as_count()?;
+    let mut vertices = Vec::new();
+    for _ in 0..vertex_count {
+        vertices.push(next_numbers(&mut lines)?.as_point()?);
+    }
+
+    indices
+        .into_iter()
+        .map(|(line, patch_indices)| {
+            let mut control_points = [Point::new_point3D(0.0, 0.0, 0.0); 16];
+            for (point, index) in control_points.iter_mut().zip(patch_indices) {
+                *point = *index
+                    .checked_sub(1)
+                    .and_then(|i| vertices.get(i))
+                    .ok_or_else(|| PatchError::Parse {
+                        line,
+                        message: format!("vertex {} does not exist", index),
+                    })?;
+            }
+            Ok(BezierPatch::new(control_points))
+        })
+        .collect()
+}
+
+#[derive(Clone)]
+struct Numbers {
+    line: usize,
+    values: Vec<f64>,
+}
+
+impl Numbers {
+    fn error(&self, message: &str) -> PatchError {
+        PatchError::Parse {
+            line: self.line,
+            message: message.to_string(),
+        }
+    }
+
+    fn count(value: f64, line: usize) -> Result<usize, PatchError> {
+        if value < 0.0 || value.fract() != 0.0 {
+            return Err(PatchError::Parse {
+                line,
+                message: format!("expected a whole number, found {}", value),
+            });
+        }
+        Ok(value as usize)
+    }
+
+    fn as_count(&self) -> Result<usize, PatchError> {
+        match self.values[..] {
+            [value] => Numbers::count(value, self.line),
+            _ => Err(self.error("expected a single count")),
+        }
+    }
+
+    fn as_point(&self) -> Result<Point<f64, 4>, PatchError> {
+        match self.values[..] {
+            [x, y, z] => Ok(Point::new_point3D(x, y, z)),
+            _ => Err(self.error("expected a point with 3 coordinates")),
+        }
+    }
+}
+
+fn next_numbers<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Numbers, PatchError> {
+    let (line, text) = lines.next().ok_or(PatchError::UnexpectedEnd)?;
+
+    let values = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token.parse::<f64>().map_err(|_| PatchError::Parse {
+                line,
+                message: format!("`{}` is not a number", token),
+            })
+        })
+        .collect::<Result<Vec<f64>, PatchError>>()?;
+
+    Ok(Numbers { line, values })
+}
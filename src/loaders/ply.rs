@@ -0,0 +1,388 @@
+use std::fs;
+use std::path::Path;
+
+use crate::color::Color;
+use crate::shapes::Mesh;
+use crate::{Point, Vector};
+
+use super::MeshError;
+
+pub fn load_ply<P: AsRef<Path>>(path: P) -> Result<Mesh, MeshError> {
+    parse_ply(&fs::read(path)?)
+}
+
+//Reads ascii and binary (little and big endian) PLY files. Vertices need
+// x, y and z, and may also have normals (nx, ny, nz), colors (red, green,
+// blue, either 0-255 integers or 0-1 floats) and texture coordinates
+// (u, v or s, t). Faces with more than 3 vertices are split into a fan of
+// triangles. Any other element or property is skipped.
+pub fn parse_ply(bytes: &[u8]) -> Result<Mesh, MeshError> {
+    let (header, body) = split_header(bytes)?;
+    let (format, elements) = parse_header(header)?;
+    let mut body = match format {
+        Format::Ascii => {
+            let text = std::str::from_utf8(body)
+                .map_err(|_| MeshError::format("ascii data is not valid text"))?;
+            Body::Ascii(text.split_ascii_whitespace())
+        }
+        Format::Binary { big_endian } => Body::Binary {
+            data: body,
+            position: 0,
+            big_endian,
+        },
+    };
+
+    let mut vertices: Option<Vec<Vec<f64>>> = None;
+    let mut vertex_properties: Vec<String> = Vec::new();
+    let mut faces: Vec<Vec<usize>> = Vec::new();
+
+    for element in &elements {
+        match element.name.as_str() {
+            "vertex" => {
+                vertex_properties = element.properties.iter().map(|p| p.name.clone()).collect();
+                let mut rows = Vec::new();
+                for _ in 0..element.count {
+                    let mut row = Vec::with_capacity(element.properties.len());
+                    for property in &element.properties {
+                        row.push(match property.kind {
+                            Kind::Scalar(scalar) => body.read(scalar)?,
+                            Kind::List(count, item) => {
+                                skip_list(&mut body, count, item)?;
+                                0.0
+                            }
+                        });
+                    }
+                    rows.push(row);
+                }
+                vertices = Some(rows);
+            }
+            "face" => {
+                for _ in 0..element.count {
+                    for property in &element.properties {
+                        match property.kind {
+                            Kind::List(count, item)
+                                if property.name == "vertex_indices"
+                                    || property.name == "vertex_index" =>
+                            {
+                                let n = body.read_index(count)?;
+                                let mut face = Vec::new();
+                                for _ in 0..n {
+                                    face.push(body.read_index(item)?);
+                                }
+                                faces.push(face);
+                            }
+                            Kind::List(count, item) => skip_list(&mut body, count, item)?,
+                            Kind::Scalar(scalar) => {
+                                body.read(scalar)?;
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {
+                for _ in 0..element.count {
+                    for property in &element.properties {
+                        match property.kind {
+                            Kind::List(count, item) => skip_list(&mut body, count, item)?,
+                            Kind::Scalar(scalar) => {
+                                body.read(scalar)?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let vertices = vertices.ok_or_else(|| MeshError::format("no vertex element"))?;
+    build_mesh(&vertices, &vertex_properties, &faces, &elements)
+}
+
+fn build_mesh(
+    vertices: &[Vec<f64>],
+    properties: &[String],
+    faces: &[Vec<usize>],
+    elements: &[Element],
+) -> Result<Mesh, MeshError> {
+    let find = |name: &str| properties.iter().position(|p| p == name);
+    let find_all = |names: [&str; 3]| -> Option<[usize; 3]> {
+        Some([find(names[0])?, find(names[1])?, find(names[2])?])
+    };
+
+    let [x, y, z] =
+        find_all(["x", "y", "z"]).ok_or_else(|| MeshError::format("vertices need x, y and z"))?;
+    let positions = vertices
+        .iter()
+        .map(|v| Point::new_point3D(v[x], v[y], v[z]))
+        .collect();
+
+    let mut triangles = Vec::new();
+    for face in faces {
+        if let Some(i) = face.iter().find(|i| **i >= vertices.len()) {
+            return Err(MeshError::format(format!("face uses missing vertex {}", i)));
+        }
+        for k in 1..face.len().saturating_sub(1) {
+            triangles.push([face[0], face[k], face[k + 1]]);
+        }
+    }
+
+    let mut mesh = Mesh::new(positions, triangles);
+
+    if let Some([nx, ny, nz]) = find_all(["nx", "ny", "nz"]) {
+        mesh = mesh.with_normals(
+            vertices
+                .iter()
+                .map(|v| Vector::new_vec3D(v[nx], v[ny], v[nz]).normalize())
+                .collect(),
+        );
+    }
+
+    if let Some([r, g, b]) = find_all(["red", "green", "blue"]) {
+        let vertex = elements.iter().find(|e| e.name == "vertex").unwrap();
+        let scale = match vertex.properties[r].kind {
+            Kind::Scalar(Scalar::Float32 | Scalar::Float64) => 1.0,
+            Kind::Scalar(scalar) => scalar.max_value(),
+            Kind::List(..) => 1.0,
+        };
+        mesh = mesh.with_colors(
+            vertices
+                .iter()
+                .map(|v| Color::new(v[r] / scale, v[g] / scale, v[b] / scale))
+                .collect(),
+        );
+    }
+
+    let uv = find("u").zip(find("v")).or(find("s").zip(find("t")));
+    if let Some((u, v)) = uv {
+        mesh = mesh.with_uvs(vertices.iter().map(|row| (row[u], row[v])).collect());
+    }
+
+    Ok(mesh)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Scalar {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl Scalar {
+    fn parse(name: &str) -> Result<Self, MeshError> {
+        Ok(match name {
+            "char" | "int8" => Scalar::Int8,
+            "uchar" | "uint8" => Scalar::UInt8,
+            "short" | "int16" => Scalar::Int16,
+            "ushort" | "uint16" => Scalar::UInt16,
+            "int" | "int32" => Scalar::Int32,
+            "uint" | "uint32" => Scalar::UInt32,
+            "float" | "float32" => Scalar::Float32,
+            "double" | "float64" => Scalar::Float64,
+            _ => return Err(MeshError::format(format!("unknown type `{}`", name))),
+        })
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Scalar::Int8 | Scalar::UInt8 => 1,
+            Scalar::Int16 | Scalar::UInt16 => 2,
+            Scalar::Int32 | Scalar::UInt32 | Scalar::Float32 => 4,
+            Scalar::Float64 => 8,
+        }
+    }
+
+    //Full intensity for colors stored as integers.
+    fn max_value(&self) -> f64 {
+        match self {
+            Scalar::Int8 => i8::MAX as f64,
+            Scalar::UInt8 => u8::MAX as f64,
+            Scalar::Int16 => i16::MAX as f64,
+            Scalar::UInt16 => u16::MAX as f64,
+            Scalar::Int32 => i32::MAX as f64,
+            Scalar::UInt32 => u32::MAX as f64,
+            Scalar::Float32 | Scalar::Float64 => 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Kind {
+    Scalar(Scalar),
+    //Type of the item count, type of the items.
+    List(Scalar, Scalar),
+}
+
+#[derive(Debug)]
+struct Property {
+    name: String,
+    kind: Kind,
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+enum Format {
+    Ascii,
+    Binary { big_endian: bool },
+}
+
+fn split_header(bytes: &[u8]) -> Result<(&str, &[u8]), MeshError> {
+    const END: &[u8] = b"end_header";
+
+    if !bytes.starts_with(b"ply") {
+        return Err(MeshError::format("missing `ply` magic number"));
+    }
+    let end = bytes
+        .windows(END.len())
+        .position(|w| w == END)
+        .ok_or_else(|| MeshError::format("missing end_header"))?;
+    let mut body = end + END.len();
+    if bytes.get(body) == Some(&b'\r') {
+        body += 1;
+    }
+    if bytes.get(body) == Some(&b'\n') {
+        body += 1;
+    }
+
+    let header = std::str::from_utf8(&bytes[..end])
+        .map_err(|_| MeshError::format("header is not valid text"))?;
+    Ok((header, &bytes[body..]))
+}
+
+fn parse_header(header: &str) -> Result<(Format, Vec<Element>), MeshError> {
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+
+    for line in header.lines().skip(1) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            [] | ["comment", ..] | ["obj_info", ..] => {}
+            ["format", name, _version] => {
+                format = Some(match name {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::Binary { big_endian: false },
+                    "binary_big_endian" => Format::Binary { big_endian: true },
+                    _ => return Err(MeshError::format(format!("unknown format `{}`", name))),
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| MeshError::format(format!("bad element count `{}`", count)))?,
+                properties: Vec::new(),
+            }),
+            ["property", "list", count, item, name] => {
+                let kind = Kind::List(Scalar::parse(count)?, Scalar::parse(item)?);
+                last_element(&mut elements)?.properties.push(Property {
+                    name: name.to_string(),
+                    kind,
+                });
+            }
+            ["property", scalar, name] => {
+                let kind = Kind::Scalar(Scalar::parse(scalar)?);
+                last_element(&mut elements)?.properties.push(Property {
+                    name: name.to_string(),
+                    kind,
+                });
+            }
+            _ => {
+                return Err(MeshError::format(format!(
+                    "unexpected header line `{}`",
+                    line
+                )))
+            }
+        }
+    }
+
+    let format = format.ok_or_else(|| MeshError::format("missing format line"))?;
+    Ok((format, elements))
+}
+
+fn last_element(elements: &mut [Element]) -> Result<&mut Element, MeshError> {
+    elements
+        .last_mut()
+        .ok_or_else(|| MeshError::format("property before any element"))
+}
+
+fn skip_list(body: &mut Body, count: Scalar, item: Scalar) -> Result<(), MeshError> {
+    let n = body.read_index(count)?;
+    for _ in 0..n {
+        body.read(item)?;
+    }
+    Ok(())
+}
+
+enum Body<'a> {
+    Ascii(std::str::SplitAsciiWhitespace<'a>),
+    Binary {
+        data: &'a [u8],
+        position: usize,
+        big_endian: bool,
+    },
+}
+
+impl Body<'_> {
+    fn read(&mut self, scalar: Scalar) -> Result<f64, MeshError> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens
+                    .next()
+                    .ok_or_else(|| MeshError::format("unexpected end of data"))?;
+                token
+                    .parse::<f64>()
+                    .map_err(|_| MeshError::format(format!("`{}` is not a number", token)))
+            }
+            Body::Binary {
+                data,
+                position,
+                big_endian,
+            } => {
+                let size = scalar.size();
+                let bytes = data
+                    .get(*position..*position + size)
+                    .ok_or_else(|| MeshError::format("unexpected end of data"))?;
+                *position += size;
+
+                let mut buffer = [0u8; 8];
+                buffer[..size].copy_from_slice(bytes);
+                if *big_endian {
+                    buffer[..size].reverse();
+                }
+
+                Ok(match scalar {
+                    Scalar::Int8 => buffer[0] as i8 as f64,
+                    Scalar::UInt8 => buffer[0] as f64,
+                    Scalar::Int16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    Scalar::UInt16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    Scalar::Int32 => i32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    Scalar::UInt32 => u32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    Scalar::Float32 => f32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    Scalar::Float64 => f64::from_le_bytes(buffer),
+                })
+            }
+        }
+    }
+
+    //A list length or vertex index, which has to be a whole number.
+    fn read_index(&mut self, scalar: Scalar) -> Result<usize, MeshError> {
+        let value = self.read(scalar)?;
+        if value.is_finite() && value >= 0.0 && value.fract() == 0.0 {
+            Ok(value as usize)
+        } else {
+            Err(MeshError::format(format!(
+                "`{}` is not a valid index",
+                value
+            )))
+        }
+    }
+}
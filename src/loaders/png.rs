@@ -0,0 +1,34 @@
+use std::fs;
+use std::path::Path;
+
+use crate::color::Color;
+use crate::Canvas;
+
+use super::CanvasError;
+
+pub fn load_png<P: AsRef<Path>>(path: P) -> Result<Canvas, CanvasError> {
+    parse_png(&fs::read(path)?)
+}
+
+//8 and 16 bit PNGs, gray or color. Like parse_ppm the values are kept as
+// they are stored, in [0, 1], and any alpha channel is dropped.
+pub fn parse_png(bytes: &[u8]) -> Result<Canvas, CanvasError> {
+    //8 bit values widen exactly, 255 * 257 = 65535
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Png)?.to_rgb16();
+    let scale = u16::MAX as f64;
+
+    let mut canvas = Canvas::new(image.width() as usize, image.height() as usize);
+    canvas.set_pixels(
+        image
+            .pixels()
+            .map(|p| {
+                Color::new(
+                    p[0] as f64 / scale,
+                    p[1] as f64 / scale,
+                    p[2] as f64 / scale,
+                )
+            })
+            .collect(),
+    );
+    Ok(canvas)
+}
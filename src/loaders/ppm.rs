@@ -0,0 +1,142 @@
+use std::fs;
+use std::path::Path;
+
+use crate::color::Color;
+use crate::Canvas;
+
+use super::CanvasError;
+
+pub fn load_ppm<P: AsRef<Path>>(path: P) -> Result<Canvas, CanvasError> {
+    parse_ppm(&fs::read(path)?)
+}
+
+//Reads plain (P3) and raw (P6) PPM. Values are divided by the maximum
+// value of the file and kept as they are stored, so images written with
+// an sRGB output transform need srgb_to_linear to get back linear light.
+// Comments run from a # to the end of the line and any amount of
+// whitespace may separate the numbers.
+pub fn parse_ppm(bytes: &[u8]) -> Result<Canvas, CanvasError> {
+    let mut tokens = Tokens { bytes, position: 0 };
+    let raw = match tokens.next() {
+        Some(b"P3") => false,
+        Some(b"P6") => true,
+        _ => return Err(CanvasError::format("not a P3 or P6 ppm file")),
+    };
+    let width = tokens.number("width")? as usize;
+    let height = tokens.number("height")? as usize;
+    let max_value = tokens.number("maximum value")?;
+    if max_value == 0 || max_value > 65535 {
+        return Err(CanvasError::format(format!(
+            "invalid maximum value {}",
+            max_value
+        )));
+    }
+    let scale = max_value as f64;
+
+    let too_large = || CanvasError::format("image too large");
+    let count = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(3))
+        .ok_or_else(too_large)?;
+    let values: Vec<u32> = if raw {
+        //exactly one whitespace byte separates the header from the data
+        if !bytes
+            .get(tokens.position)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            return Err(CanvasError::format(
+                "expected whitespace between the header and the pixel data",
+            ));
+        }
+        let start = tokens.position + 1;
+        let size = if max_value < 256 { 1 } else { 2 };
+        let end = count
+            .checked_mul(size)
+            .and_then(|length| length.checked_add(start))
+            .ok_or_else(too_large)?;
+        let data = bytes
+            .get(start..end)
+            .ok_or_else(|| CanvasError::format("the pixel data is truncated"))?;
+        if size == 1 {
+            data.iter().map(|b| *b as u32).collect()
+        } else {
+            data.chunks_exact(2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]) as u32)
+                .collect()
+        }
+    } else {
+        (0..count)
+            .map(|_| tokens.number("pixel value"))
+            .collect::<Result<_, _>>()?
+    };
+
+    if let Some(value) = values.iter().find(|value| **value > max_value) {
+        return Err(CanvasError::format(format!(
+            "pixel value {} is above the maximum value",
+            value
+        )));
+    }
+
+    let mut canvas = Canvas::new(width, height);
+    canvas.set_pixels(
+        values
+            .chunks_exact(3)
+            .map(|rgb| {
+                Color::new(
+                    rgb[0] as f64 / scale,
+                    rgb[1] as f64 / scale,
+                    rgb[2] as f64 / scale,
+                )
+            })
+            .collect(),
+    );
+    Ok(canvas)
+}
+
+struct Tokens<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Tokens<'a> {
+    //The next whitespace separated word, skipping comments.
+    fn next(&mut self) -> Option<&'a [u8]> {
+        loop {
+            match self.bytes.get(self.position)? {
+                b'#' => {
+                    while !matches!(self.bytes.get(self.position), Some(b'\n' | b'\r') | None) {
+                        self.position += 1;
+                    }
+                }
+                c if c.is_ascii_whitespace() => self.position += 1,
+                _ => break,
+            }
+        }
+
+        let start = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(|c| !c.is_ascii_whitespace() && *c != b'#')
+        {
+            self.position += 1;
+        }
+        Some(&self.bytes[start..self.position])
+    }
+
+    fn number(&mut self, what: &str) -> Result<u32, CanvasError> {
+        let token = self
+            .next()
+            .ok_or_else(|| CanvasError::format(format!("missing {}", what)))?;
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .ok_or_else(|| {
+                CanvasError::format(format!(
+                    "invalid {} '{}'",
+                    what,
+                    String::from_utf8_lossy(token)
+                ))
+            })
+    }
+}
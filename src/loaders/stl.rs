@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::shapes::Mesh;
+use crate::Point;
+
+use super::MeshError;
+
+const HEADER_SIZE: usize = 80;
+const TRIANGLE_SIZE: usize = 50;
+
+pub fn load_stl<P: AsRef<Path>>(path: P) -> Result<Mesh, MeshError> {
+    parse_stl(&fs::read(path)?)
+}
+
+//Reads ascii and binary STL. Binary files may also start with "solid",
+// so the format is told apart by whether the size matches the triangle
+// count of a binary file. Vertices shared by several facets are merged,
+// the stored facet normals are ignored in favour of the winding order.
+pub fn parse_stl(bytes: &[u8]) -> Result<Mesh, MeshError> {
+    let corners = if is_binary(bytes) {
+        parse_binary(bytes)?
+    } else {
+        parse_ascii(bytes)?
+    };
+
+    let mut positions = Vec::new();
+    let mut indices: HashMap<[u64; 3], usize> = HashMap::new();
+    let mut triangles = Vec::with_capacity(corners.len() / 3);
+
+    for triangle in corners.chunks(3) {
+        let mut indexed = [0; 3];
+        for (index, corner) in indexed.iter_mut().zip(triangle) {
+            let key = corner.map(f64::to_bits);
+            *index = *indices.entry(key).or_insert_with(|| {
+                positions.push(Point::new_point3D(corner[0], corner[1], corner[2]));
+                positions.len() - 1
+            });
+        }
+        triangles.push(indexed);
+    }
+
+    Ok(Mesh::new(positions, triangles))
+}
+
+fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.len() < HEADER_SIZE + 4 {
+        return false;
+    }
+    let count = u32::from_le_bytes(bytes[HEADER_SIZE..HEADER_SIZE + 4].try_into().unwrap());
+
+    bytes.len() == HEADER_SIZE + 4 + count as usize * TRIANGLE_SIZE || !bytes.starts_with(b"solid")
+}
+
+fn parse_binary(bytes: &[u8]) -> Result<Vec<[f64; 3]>, MeshError> {
+    let count =
+        u32::from_le_bytes(bytes[HEADER_SIZE..HEADER_SIZE + 4].try_into().unwrap()) as usize;
+    let data = &bytes[HEADER_SIZE + 4..];
+    if data.len() < count * TRIANGLE_SIZE {
+        return Err(MeshError::format(format!(
+            "expected {} triangles, the file is too short",
+            count
+        )));
+    }
+
+    let float =
+        |offset: usize| f32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as f64;
+    let mut corners = Vec::with_capacity(count * 3);
+    for triangle in 0..count {
+        //12 bytes of normal first, 2 bytes of attributes last.
+        let start = triangle * TRIANGLE_SIZE + 12;
+        for corner in 0..3 {
+            let offset = start + corner * 12;
+            corners.push([float(offset), float(offset + 4), float(offset + 8)]);
+        }
+    }
+
+    Ok(corners)
+}
+
+fn parse_ascii(bytes: &[u8]) -> Result<Vec<[f64; 3]>, MeshError> {
+    let text = std::str::from_utf8(bytes).map_err(|_| MeshError::format("not valid text"))?;
+    let mut tokens = text.split_ascii_whitespace();
+    if tokens.next() != Some("solid") {
+        return Err(MeshError::format("missing `solid`"));
+    }
+
+    let mut corners = Vec::new();
+    //How many vertices the facet being read has so far.
+    let mut in_facet = None;
+    while let Some(token) = tokens.next() {
+        match token {
+            "facet" => in_facet = Some(0),
+            "vertex" => {
+                let mut corner = [0.0; 3];
+                for value in corner.iter_mut() {
+                    let token = tokens
+                        .next()
+                        .ok_or_else(|| MeshError::format("unexpected end of file"))?;
+                    *value = token
+                        .parse()
+                        .map_err(|_| MeshError::format(format!("`{}` is not a number", token)))?;
+                }
+                corners.push(corner);
+                if let Some(count) = in_facet.as_mut() {
+                    *count += 1;
+                }
+            }
+            "endfacet" => {
+                if in_facet != Some(3) {
+                    return Err(MeshError::format("a facet needs exactly 3 vertices"));
+                }
+                in_facet = None;
+            }
+            _ => {}
+        }
+    }
+    if in_facet.is_some() {
+        return Err(MeshError::format("unexpected end of file inside a facet"));
+    }
+    if corners.len() % 3 != 0 {
+        return Err(MeshError::format("vertices outside of a facet"));
+    }
+
+    Ok(corners)
+}
@@ -1,6 +1,6 @@
-use crate::{color::Color, Light, Object, Pattern, PatternType, Point, Vector};
+use crate::{color::Color, Bump, Light, Object, Pattern, PatternType, Point, Vector};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Material {
     pub ambient: f64,
     pub color: Color,
@@ -10,9 +10,19 @@ pub struct Material {
     pub reflective: f64,
     pub transparency: f64,
     pub refractive_index: f64,
+    pub absorption: Color,
+    //How strongly refraction depends on wavelength, the lower the
+    // stronger (diamond ~55, flint glass ~30). 0.0 disables dispersion.
+    pub abbe_number: f64,
     pub pattern: Pattern,
+    pub bump: Bump,
 }
 
+//Fraunhofer lines used to define the Abbe number, in nanometers.
+const WAVELENGTH_D: f64 = 587.6;
+const WAVELENGTH_F: f64 = 486.1;
+const WAVELENGTH_C: f64 = 656.3;
+
 impl Material {
     pub fn new(
         ambient: f64,
@@ -34,7 +44,10 @@ impl Material {
             reflective,
             transparency,
             refractive_index,
+            absorption: Color::black(),
+            abbe_number: 0.0,
             pattern,
+            bump: Bump::None,
         }
     }
 
@@ -65,6 +78,52 @@ impl Material {
             Pattern::with_type(PatternType::None),
         )
     }
+    //Cauchy's equation n = A + B / wavelength^2, fitted so that
+    // refractive_index is the index at the d line and the F to C
+    // spread matches the Abbe number.
+    pub fn refractive_index_at(&self, wavelength: f64) -> f64 {
+        if self.abbe_number <= 0.0 {
+            return self.refractive_index;
+        }
+
+        let spread = (self.refractive_index - 1.0) / self.abbe_number;
+        let b = spread / (WAVELENGTH_F.powi(-2) - WAVELENGTH_C.powi(-2));
+        let a = self.refractive_index - b / WAVELENGTH_D.powi(2);
+
+        a + b / wavelength.powi(2)
+    }
+
+    //Beer-Lambert law: the fraction of light that survives after travelling
+    // `distance` units inside this material, per color channel.
+    pub fn transmittance(&self, distance: f64) -> Color {
+        let attenuate = |coefficient: f64| {
+            if coefficient <= 0.0 {
+                1.0
+            } else {
+                f64::exp(-coefficient * distance)
+            }
+        };
+
+        Color::new(
+            attenuate(self.absorption.red),
+            attenuate(self.absorption.green),
+            attenuate(self.absorption.blue),
+        )
+    }
+
+    //The surface color before any lighting, from the pattern if there is
+    // one. Vertex colors (e.g. from a scanned mesh) are tinted by the
+    // material color.
+    pub fn color_at(&self, object: &Object, point: &Point<f64, 4>) -> Color {
+        if self.pattern.p_type != PatternType::None {
+            object.pattern_at_object(point).unwrap()
+        } else if let Some(vertex_color) = object.vertex_color_at(point) {
+            self.color * vertex_color
+        } else {
+            self.color
+        }
+    }
+
    pub fn lightning(
         &self,
         object: &Object,
@@ -73,23 +132,35 @@ impl Material {
         eyev: &Vector<f64, 4>,
         normalv: &Vector<f64, 4>,
         shadowed: bool,
+    ) -> Color {
+        let transmittance = if shadowed {
+            Color::black()
+        } else {
+            Color::white()
+        };
+
+        self.lightning_with_transmittance(object, light, point, eyev, normalv, transmittance)
+    }
+
+    //Same as lightning, but the light reaching the point may be partially
+    // blocked and tinted by the objects in between (see World::shadow_transmittance).
+    pub fn lightning_with_transmittance(
+        &self,
+        object: &Object,
+        light: &Light,
+        point: &Point<f64, 4>,
+        eyev: &Vector<f64, 4>,
+        normalv: &Vector<f64, 4>,
+        transmittance: Color,
     ) -> Color {
         let diffuse: Color;
         let specular: Color;
 
-        let color = {
-            if self.pattern.p_type != PatternType::None {
-                object.pattern_at_object(point).unwrap()
-            } else {
-                self.color
-            }
-        };
-
-        let effective_color = color * light.intensity;
+        let effective_color = self.color_at(object, point) * light.intensity;
         let lightv = Vector::normalize(light.position - *point);
         let ambient = effective_color * self.ambient;
 
-        if shadowed {
+        if transmittance == Color::black() {
             return ambient;
         }
 
@@ -118,6 +189,6 @@ impl Material {
             }
         }
 
-        ambient + diffuse + specular
+        ambient + (diffuse + specular) * transmittance
     }
 }
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::Point;
+
+//Ken Perlin's improved gradient noise, with fractal sums built on top.
+// The same seed always gives the same noise, so renders are reproducible.
+#[derive(Clone)]
+pub struct Noise {
+    seed: u64,
+    permutation: Arc<[u8; 512]>,
+}
+
+impl Noise {
+    pub fn new(seed: u64) -> Self {
+        let mut table = [0u8; 256];
+        for (i, entry) in table.iter_mut().enumerate() {
+            *entry = i as u8;
+        }
+
+        //Fisher-Yates shuffle driven by splitmix64
+        let mut state = seed;
+        for i in (1..256).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+
+        //doubled so lookups never have to wrap around
+        let mut permutation = [0u8; 512];
+        for (i, entry) in permutation.iter_mut().enumerate() {
+            *entry = table[i & 255];
+        }
+
+        Self {
+            seed,
+            permutation: Arc::new(permutation),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    //Roughly in [-1, 1], 0 at every integer lattice point.
+    pub fn noise(&self, point: &Point<f64, 4>) -> f64 {
+        let (x, y, z) = (point[0], point[1], point[2]);
+
+        // unit cube that contains the point
+        let xi = (x.floor() as i64 & 255) as usize;
+        let yi = (y.floor() as i64 & 255) as usize;
+        let zi = (z.floor() as i64 & 255) as usize;
+
+        // relative position of the point in that cube
+        let x = x - x.floor();
+        let y = y - y.floor();
+        let z = z - z.floor();
+
+        let u = fade(x);
+        let v = fade(y);
+        let w = fade(z);
+
+        let p = &self.permutation;
+        let a = p[xi] as usize + yi;
+        let aa = p[a] as usize + zi;
+        let ab = p[a + 1] as usize + zi;
+        let b = p[xi + 1] as usize + yi;
+        let ba = p[b] as usize + zi;
+        let bb = p[b + 1] as usize + zi;
+
+        // blend the contributions of the 8 corners
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(p[aa], x, y, z), grad(p[ba], x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(p[ab], x, y - 1.0, z),
+                    grad(p[bb], x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(p[aa + 1], x, y, z - 1.0),
+                    grad(p[ba + 1], x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(p[ab + 1], x, y - 1.0, z - 1.0),
+                    grad(p[bb + 1], x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+
+    //Fractal brownian motion: `octaves` layers of noise, each with twice
+    // the frequency and half the amplitude of the previous. Kept in [-1, 1].
+    pub fn fbm(&self, point: &Point<f64, 4>, octaves: u32) -> f64 {
+        self.fractal(point, octaves, |n| n)
+    }
+
+    //Like fbm but summing the absolute value of each layer, which gives
+    // the sharp creases seen in marble and flames. In [0, 1].
+    pub fn turbulence(&self, point: &Point<f64, 4>, octaves: u32) -> f64 {
+        self.fractal(point, octaves, f64::abs)
+    }
+
+    fn fractal<F>(&self, point: &Point<f64, 4>, octaves: u32, layer: F) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        let mut sum = 0.0;
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+
+        for _ in 0..octaves.max(1) {
+            let p = Point::new_point3D(
+                point[0] * frequency,
+                point[1] * frequency,
+                point[2] * frequency,
+            );
+            sum += layer(self.noise(&p)) * amplitude;
+            total += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        sum / total
+    }
+}
+
+//The permutation is derived from the seed, so comparing seeds is enough.
+impl PartialEq for Noise {
+    fn eq(&self, other: &Self) -> bool {
+        self.seed == other.seed
+    }
+}
+
+impl fmt::Debug for Noise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Noise").field("seed", &self.seed).finish()
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+// 6t^5 - 15t^4 + 10t^3, smooths the interpolation near the cube faces
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+//Dot product between the offset and one of 12 gradient directions.
+fn grad(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
@@ -1,10 +1,15 @@
+use crate::shapes::{Annulus, Bounds, Disk, Heightfield, Mesh, Rectangle, SdfShape, Torus};
 use crate::transformation::*;
-use crate::{color::Color, Material, Matrix, Motion, Pattern, Point, Ray, Shape, Vector};
+use crate::{
+    color::Color, Bump, Material, Matrix, Motion, Pattern, Point, Ray, Sdf, Shape, SurfacePoint,
+    Vector,
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Object {
     pub shape: Shape,
     pub material: Material,
+    pub casts_shadow: bool,
     transformation: Matrix<f64, 4, 4>,
     inverse_transformation: Matrix<f64, 4, 4>,
     inverse_transpose: Matrix<f64, 4, 4>,
@@ -14,60 +19,60 @@ impl Motion for Object {
     fn move_front(&mut self, value: f64) -> Self {
         let move_front = translation(0.0, 0.0, value) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
 
     fn move_back(&mut self, value: f64) -> Self {
         let move_front = translation(0.0, 0.0, -value) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
 
     fn move_left(&mut self, value: f64) -> Self {
         let move_front = translation(-value, 0.0, 0.0) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
 
     fn move_right(&mut self, value: f64) -> Self {
         let move_front = translation(value, 0.0, 0.0) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
 
     fn move_up(&mut self, value: f64) -> Self {
         let move_front = translation(0.0, value, 0.0) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
 
     fn move_down(&mut self, value: f64) -> Self {
         let move_front = translation(0.0, -value, 0.0) * self.transformation;
         self.set_transformation(move_front);
-        *self
+        self.clone()
     }
     fn look_left(&mut self, degree: f64) -> Self {
         let look = rotation_y(degree.to_radians()) * self.transformation;
         self.set_transformation(look);
-        *self
+        self.clone()
     }
 
     fn look_right(&mut self, degree: f64) -> Self {
         let look = rotation_y(-degree.to_radians()) * self.transformation;
         self.set_transformation(look);
-        *self
+        self.clone()
     }
 
     fn look_up(&mut self, degree: f64) -> Self {
         let look = rotation_x(-degree.to_radians()) * self.transformation;
         self.set_transformation(look);
-        *self
+        self.clone()
     }
 
     fn look_down(&mut self, degree: f64) -> Self {
         let look = rotation_x(degree.to_radians()) * self.transformation;
         self.set_transformation(look);
-        *self
+        self.clone()
     }
 }
 
@@ -78,6 +83,7 @@ impl Object {
         Self {
             shape,
             material: Material::default(),
+            casts_shadow: true,
             transformation,
             inverse_transformation: inverse,
             inverse_transpose: transpose,
@@ -88,6 +94,7 @@ impl Object {
         Self {
             shape: Shape::Plane,
             material: Material::default(),
+            casts_shadow: true,
             transformation: iden,
             inverse_transformation: iden,
             inverse_transpose: iden,
@@ -102,6 +109,7 @@ impl Object {
         Self {
             shape: Shape::Sphere,
             material,
+            casts_shadow: true,
             transformation: iden,
             inverse_transformation: iden,
             inverse_transpose: iden,
@@ -113,12 +121,50 @@ impl Object {
         Self {
             shape: Shape::Sphere,
             material: Material::default(),
+            casts_shadow: true,
             transformation: iden,
             inverse_transformation: iden,
             inverse_transpose: iden,
         }
     }
 
+    pub fn new_disk(radius: f64) -> Self {
+        Object::new(Shape::Disk(Disk::new(radius)), Matrix::iden())
+    }
+
+    pub fn new_rectangle(half_width: f64, half_depth: f64) -> Self {
+        Object::new(
+            Shape::Rectangle(Rectangle::new(half_width, half_depth)),
+            Matrix::iden(),
+        )
+    }
+
+    pub fn new_annulus(inner_radius: f64, outer_radius: f64) -> Self {
+        Object::new(
+            Shape::Annulus(Annulus::new(inner_radius, outer_radius)),
+            Matrix::iden(),
+        )
+    }
+
+    pub fn new_torus(major_radius: f64, minor_radius: f64) -> Self {
+        Object::new(
+            Shape::Torus(Torus::new(major_radius, minor_radius)),
+            Matrix::iden(),
+        )
+    }
+
+    pub fn new_sdf(sdf: Sdf) -> Self {
+        Object::new(Shape::Sdf(SdfShape::new(sdf)), Matrix::iden())
+    }
+
+    pub fn new_heightfield(heightfield: Heightfield) -> Self {
+        Object::new(Shape::Heightfield(heightfield), Matrix::iden())
+    }
+
+    pub fn new_mesh(mesh: Mesh) -> Self {
+        Object::new(Shape::Mesh(mesh), Matrix::iden())
+    }
+
     pub fn with_color(mut self, color: Color) -> Self {
         self.material.color = color;
         self
@@ -144,6 +190,16 @@ impl Object {
         self
     }
 
+    pub fn with_casts_shadow(mut self, casts_shadow: bool) -> Self {
+        self.casts_shadow = casts_shadow;
+        self
+    }
+
+    pub fn with_bump(mut self, bump: Bump) -> Self {
+        self.material.bump = bump;
+        self
+    }
+
     pub fn set_material(&mut self, material: Material) {
         self.material = material;
     }
@@ -181,13 +237,35 @@ impl Object {
         let mut world_normal = self.inverse_transpose * local_normal;
         let w = world_normal.get_mut(3).unwrap();
         *w = 0.0;
-        world_normal.normalize()
+        self.material.bump.perturb_on_surface(
+            &SurfacePoint::new(&self.shape, local_point),
+            &local_point,
+            world_normal.normalize(),
+            &self.inverse_transpose,
+        )
+    }
+
+    //World space box around the transformed shape.
+    pub fn bounds(&self) -> Bounds {
+        self.shape.bounds().transform(&self.transformation)
+    }
+
+    pub fn uv_at(&self, world_point: &Point<f64, 4>) -> (f64, f64) {
+        let object_point = self.inverse_transformation * *world_point;
+        self.shape.local_uv(&object_point)
+    }
+
+    pub fn vertex_color_at(&self, world_point: &Point<f64, 4>) -> Option<Color> {
+        let object_point = self.inverse_transformation * *world_point;
+        self.shape.vertex_color_at(&object_point)
     }
 
     pub fn pattern_at_object(&self, world_point: &Point<f64, 4>) -> Option<Color> {
         let object_point = self.inverse_transformation * *world_point;
-        let pattern_point = self.material.pattern.inverse * object_point;
+        let surface = SurfacePoint::new(&self.shape, object_point);
 
-        self.material.pattern.pattern_at(&pattern_point)
+        self.material
+            .pattern
+            .pattern_on_surface(&object_point, &surface)
     }
 }
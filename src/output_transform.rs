@@ -0,0 +1,116 @@
+use crate::color::Color;
+
+//Squeezes unbounded scene values into [0, 1].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToneMap {
+    //Anything above 1 is cut off.
+    Clamp,
+    //x / (1 + x), never quite reaching white.
+    Reinhard,
+    //Reinhard that maps `white` (and anything brighter) to 1.
+    ExtendedReinhard { white: f64 },
+    //Krzysztof Narkowicz's fit of the ACES filmic curve.
+    Aces,
+}
+
+impl ToneMap {
+    pub fn map(&self, value: f64) -> f64 {
+        let value = value.max(0.0);
+        let mapped = match self {
+            ToneMap::Clamp => value,
+            ToneMap::Reinhard => value / (1.0 + value),
+            ToneMap::ExtendedReinhard { white } => {
+                value * (1.0 + value / (white * white)) / (1.0 + value)
+            }
+            ToneMap::Aces => {
+                (value * (2.51 * value + 0.03)) / (value * (2.43 * value + 0.59) + 0.14)
+            }
+        };
+        mapped.clamp(0.0, 1.0)
+    }
+}
+
+//Turns the linear colors of a render into what is written to 8 bit
+// images: exposure, then tone mapping, then the sRGB transfer curve.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutputTransform {
+    //In stops, every +1 doubles the brightness.
+    pub exposure: f64,
+    pub tone_map: ToneMap,
+    //Without it the values are written as they are (linear).
+    pub srgb: bool,
+}
+
+impl OutputTransform {
+    pub fn new() -> Self {
+        Self {
+            exposure: 0.0,
+            tone_map: ToneMap::Clamp,
+            srgb: true,
+        }
+    }
+
+    pub fn with_exposure(mut self, exposure: f64) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    pub fn with_tone_map(mut self, tone_map: ToneMap) -> Self {
+        self.tone_map = tone_map;
+        self
+    }
+
+    pub fn with_srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    //The display color, every channel in [0, 1].
+    pub fn apply(&self, color: &Color) -> Color {
+        let scale = 2.0f64.powf(self.exposure);
+        let channel = |value: f64| {
+            let mapped = self.tone_map.map(value * scale);
+            if self.srgb {
+                linear_to_srgb(mapped)
+            } else {
+                mapped
+            }
+        };
+
+        Color::new(
+            channel(color.red),
+            channel(color.green),
+            channel(color.blue),
+        )
+    }
+
+    pub fn to_rgb8(&self, color: &Color) -> [u8; 3] {
+        let color = self.apply(color);
+        [color.red, color.green, color.blue].map(|c| quantize(c, u8::MAX as f64) as u8)
+    }
+
+    pub fn to_rgb16(&self, color: &Color) -> [u16; 3] {
+        let color = self.apply(color);
+        [color.red, color.green, color.blue].map(|c| quantize(c, u16::MAX as f64) as u16)
+    }
+}
+
+impl Default for OutputTransform {
+    fn default() -> Self {
+        OutputTransform::new()
+    }
+}
+
+//Inverse of srgb_to_linear.
+pub fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+//Rounds to the nearest of the levels from 0 to `max`.
+fn quantize(value: f64, max: f64) -> f64 {
+    (value.clamp(0.0, 1.0) * max).round()
+}
@@ -0,0 +1,38 @@
+use crate::{color::Color, Point};
+
+use super::{LocalPattern, Slot, SurfacePoint};
+
+//The sum of two patterns, e.g. to lay highlights over a base.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Additive {
+    pub pattern_a: Slot,
+    pub pattern_b: Slot,
+}
+
+impl Additive {
+    pub fn new(pattern_a: impl Into<Slot>, pattern_b: impl Into<Slot>) -> Self {
+        Self {
+            pattern_a: pattern_a.into(),
+            pattern_b: pattern_b.into(),
+        }
+    }
+
+    pub fn additive_at(&self, point: &Point<f64, 4>) -> Color {
+        self.additive_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn additive_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Color {
+        self.pattern_a.slot_at_surface(point, surface)
+            + self.pattern_b.slot_at_surface(point, surface)
+    }
+}
+
+impl LocalPattern for Additive {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.additive_at(point)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::color::Color;
+
+//A solid color with a different color in each corner, useful to check
+// that a mapping is oriented the way it should be.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AlignCheck {
+    pub main: Color,
+    pub upper_left: Color,
+    pub upper_right: Color,
+    pub bottom_left: Color,
+    pub bottom_right: Color,
+}
+
+impl AlignCheck {
+    pub fn new(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> Self {
+        Self {
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        }
+    }
+
+    pub fn align_check_at(&self, u: f64, v: f64) -> Color {
+        // remember: v = 0 at the bottom, v = 1 at the top
+        if v > 0.8 {
+            if u < 0.2 {
+                return self.upper_left;
+            }
+            if u > 0.8 {
+                return self.upper_right;
+            }
+        } else if v < 0.2 {
+            if u < 0.2 {
+                return self.bottom_left;
+            }
+            if u > 0.8 {
+                return self.bottom_right;
+            }
+        }
+
+        self.main
+    }
+}
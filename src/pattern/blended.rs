@@ -0,0 +1,39 @@
+use crate::{color::Color, Point};
+
+use super::{LocalPattern, Slot, SurfacePoint};
+
+//The average of two patterns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Blended {
+    pub pattern_a: Slot,
+    pub pattern_b: Slot,
+}
+
+impl Blended {
+    pub fn new(pattern_a: impl Into<Slot>, pattern_b: impl Into<Slot>) -> Self {
+        Self {
+            pattern_a: pattern_a.into(),
+            pattern_b: pattern_b.into(),
+        }
+    }
+
+    pub fn blended_at(&self, point: &Point<f64, 4>) -> Color {
+        self.blended_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn blended_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Color {
+        (self.pattern_a.slot_at_surface(point, surface)
+            + self.pattern_b.slot_at_surface(point, surface))
+            * 0.5
+    }
+}
+
+impl LocalPattern for Blended {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.blended_at(point)
+    }
+}
@@ -1,25 +1,44 @@
 use crate::{color::Color, ApproximateEq, Point};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use super::{LocalPattern, Slot, SurfacePoint};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Checker {
-    pub color_a: Color,
-    pub color_b: Color,
+    pub color_a: Slot,
+    pub color_b: Slot,
 }
 
 impl Checker {
-    pub fn new(color_a: Color, color_b: Color) -> Self {
-        Self { color_a, color_b }
+    pub fn new(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> Self {
+        Self {
+            color_a: color_a.into(),
+            color_b: color_b.into(),
+        }
     }
 
     pub fn checker_at(&self, point: &Point<f64, 4>) -> Color {
+        self.checker_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn checker_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Color {
         let x = point.get(0).unwrap().floor();
         let y = point.get(1).unwrap().floor();
         let z = point.get(2).unwrap().floor();
 
         if ((x + y + z) as i64 % 2) == 0 {
-            self.color_a.clone()
+            self.color_a.slot_at_surface(point, surface)
         } else {
-            self.color_b.clone()
+            self.color_b.slot_at_surface(point, surface)
         }
     }
 }
+
+impl LocalPattern for Checker {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.checker_at(point)
+    }
+}
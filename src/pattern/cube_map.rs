@@ -0,0 +1,109 @@
+use crate::{color::Color, Point};
+
+use super::{LocalPattern, UvPattern};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Front,
+    Right,
+    Back,
+    Up,
+    Down,
+}
+
+impl CubeFace {
+    //The face of the cube (-1..1 on every axis) a point belongs to,
+    // picked by its largest coordinate.
+    pub fn from_point(point: &Point<f64, 4>) -> CubeFace {
+        let x = point[0];
+        let y = point[1];
+        let z = point[2];
+        let coord = x.abs().max(y.abs()).max(z.abs());
+
+        if coord == x {
+            CubeFace::Right
+        } else if coord == -x {
+            CubeFace::Left
+        } else if coord == y {
+            CubeFace::Up
+        } else if coord == -y {
+            CubeFace::Down
+        } else if coord == z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+
+    //uv coordinates of a point on this face, as seen from outside the cube.
+    pub fn uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        let x = point[0];
+        let y = point[1];
+        let z = point[2];
+
+        let (u, v) = match self {
+            CubeFace::Front => (x + 1.0, y + 1.0),
+            CubeFace::Back => (1.0 - x, y + 1.0),
+            CubeFace::Left => (z + 1.0, y + 1.0),
+            CubeFace::Right => (1.0 - z, y + 1.0),
+            CubeFace::Up => (x + 1.0, 1.0 - z),
+            CubeFace::Down => (x + 1.0, z + 1.0),
+        };
+
+        (u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0)
+    }
+}
+
+//A different uv pattern on each face of a cube.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CubeMap {
+    pub left: UvPattern,
+    pub front: UvPattern,
+    pub right: UvPattern,
+    pub back: UvPattern,
+    pub up: UvPattern,
+    pub down: UvPattern,
+}
+
+impl CubeMap {
+    pub fn new(
+        left: UvPattern,
+        front: UvPattern,
+        right: UvPattern,
+        back: UvPattern,
+        up: UvPattern,
+        down: UvPattern,
+    ) -> Self {
+        Self {
+            left,
+            front,
+            right,
+            back,
+            up,
+            down,
+        }
+    }
+
+    pub fn cube_map_at(&self, point: &Point<f64, 4>) -> Color {
+        let face = CubeFace::from_point(point);
+        let (u, v) = face.uv(point);
+
+        let pattern = match face {
+            CubeFace::Left => &self.left,
+            CubeFace::Front => &self.front,
+            CubeFace::Right => &self.right,
+            CubeFace::Back => &self.back,
+            CubeFace::Up => &self.up,
+            CubeFace::Down => &self.down,
+        };
+
+        pattern.uv_pattern_at(u, v)
+    }
+}
+
+impl LocalPattern for CubeMap {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.cube_map_at(point)
+    }
+}
@@ -1,21 +1,41 @@
 use crate::{color::Color, Point};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use super::{LocalPattern, Slot, SurfacePoint};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Gradient {
-    pub color_a: Color,
-    pub color_b: Color,
+    pub color_a: Slot,
+    pub color_b: Slot,
 }
 
 impl Gradient {
-    pub fn new(color_a: Color, color_b: Color) -> Self {
-        Self { color_a, color_b }
+    pub fn new(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> Self {
+        Self {
+            color_a: color_a.into(),
+            color_b: color_b.into(),
+        }
     }
 
     pub fn gradient_at(&self, point: &Point<f64, 4>) -> Color {
-        let distance = self.color_b - self.color_a;
+        self.gradient_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn gradient_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Color {
+        let color_a = self.color_a.slot_at_surface(point, surface);
+        let distance = self.color_b.slot_at_surface(point, surface) - color_a;
         let x = point.get(0).unwrap();
         let fraction = x - x.floor();
 
-        self.color_a + distance * fraction
+        color_a + distance * fraction
+    }
+}
+
+impl LocalPattern for Gradient {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.gradient_at(point)
     }
 }
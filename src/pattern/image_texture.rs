@@ -0,0 +1,201 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use image::error::{ParameterError, ParameterErrorKind};
+use image::{DynamicImage, ImageError};
+
+use crate::color::Color;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Filter {
+    Nearest,
+    Bilinear,
+}
+
+//What happens to uv coordinates outside of [0, 1].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Wrap {
+    Repeat,
+    Clamp,
+    Mirror,
+}
+
+impl Wrap {
+    //Maps a texel index that may lie outside the image back into 0..size.
+    fn address(&self, i: i64, size: usize) -> usize {
+        let size = size as i64;
+        let i = match self {
+            Wrap::Repeat => i.rem_euclid(size),
+            Wrap::Clamp => i.clamp(0, size - 1),
+            Wrap::Mirror => {
+                let m = i.rem_euclid(2 * size);
+                if m < size {
+                    m
+                } else {
+                    2 * size - 1 - m
+                }
+            }
+        };
+        i as usize
+    }
+}
+
+//Decoded texels in linear color, row 0 being the top of the image.
+struct TextureData {
+    width: usize,
+    height: usize,
+    texels: Vec<Color>,
+}
+
+//An image sampled by uv coordinates. The texels are shared between
+// clones, so copying a material that uses it is cheap.
+#[derive(Clone)]
+pub struct ImageTexture {
+    data: Arc<TextureData>,
+    pub filter: Filter,
+    pub wrap: Wrap,
+}
+
+impl ImageTexture {
+    //Loads a color texture (png, jpeg, ...). 8 and 16 bit images are
+    // assumed to be sRGB encoded and are converted to linear color.
+    pub fn load<P: AsRef<Path>>(path: P) -> image::ImageResult<Self> {
+        ImageTexture::from_image(&image::open(path)?, true)
+    }
+
+    //Loads a texture that holds data instead of colors, e.g. a normal map,
+    // keeping its values as they are stored.
+    pub fn load_linear<P: AsRef<Path>>(path: P) -> image::ImageResult<Self> {
+        ImageTexture::from_image(&image::open(path)?, false)
+    }
+
+    //Fails for images without any pixels.
+    pub fn from_image(image: &DynamicImage, srgb: bool) -> image::ImageResult<Self> {
+        if image.width() == 0 || image.height() == 0 {
+            return Err(ImageError::Parameter(ParameterError::from_kind(
+                ParameterErrorKind::Generic("texture image is empty".to_string()),
+            )));
+        }
+
+        //float images (hdr, exr) are always linear
+        let srgb = srgb
+            && !matches!(
+                image,
+                DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_)
+            );
+        let decode = |c: f32| {
+            if srgb {
+                srgb_to_linear(c as f64)
+            } else {
+                c as f64
+            }
+        };
+
+        let rgb = image.to_rgb32f();
+        let texels = rgb
+            .pixels()
+            .map(|p| Color::new(decode(p[0]), decode(p[1]), decode(p[2])))
+            .collect();
+
+        Ok(ImageTexture::from_colors(
+            rgb.width() as usize,
+            rgb.height() as usize,
+            texels,
+        ))
+    }
+
+    //`texels` are linear colors, row by row starting at the top. The
+    // texture needs at least one texel.
+    pub fn from_colors(width: usize, height: usize, texels: Vec<Color>) -> Self {
+        assert!(width > 0 && height > 0);
+        assert_eq!(width * height, texels.len());
+
+        Self {
+            data: Arc::new(TextureData {
+                width,
+                height,
+                texels,
+            }),
+            filter: Filter::Bilinear,
+            wrap: Wrap::Repeat,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.data.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.data.height
+    }
+
+    fn texel(&self, x: i64, y: i64) -> Color {
+        let x = self.wrap.address(x, self.data.width);
+        let y = self.wrap.address(y, self.data.height);
+        self.data.texels[y * self.data.width + x]
+    }
+
+    pub fn uv_texture_at(&self, u: f64, v: f64) -> Color {
+        // v = 0 is the bottom of the texture but the last row of the image
+        let x = u * self.data.width as f64;
+        let y = (1.0 - v) * self.data.height as f64;
+
+        match self.filter {
+            Filter::Nearest => self.texel(x.floor() as i64, y.floor() as i64),
+            Filter::Bilinear => {
+                //texel centers sit at half integer coordinates
+                let x = x - 0.5;
+                let y = y - 0.5;
+                let x0 = x.floor();
+                let y0 = y.floor();
+                let tx = x - x0;
+                let ty = y - y0;
+                let (x0, y0) = (x0 as i64, y0 as i64);
+
+                let top = self.texel(x0, y0) * (1.0 - tx) + self.texel(x0 + 1, y0) * tx;
+                let bottom = self.texel(x0, y0 + 1) * (1.0 - tx) + self.texel(x0 + 1, y0 + 1) * tx;
+
+                top * (1.0 - ty) + bottom * ty
+            }
+        }
+    }
+}
+
+//Two textures are the same only if they share their texels.
+impl PartialEq for ImageTexture {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+            && self.filter == other.filter
+            && self.wrap == other.wrap
+    }
+}
+
+impl fmt::Debug for ImageTexture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ImageTexture")
+            .field("width", &self.data.width)
+            .field("height", &self.data.height)
+            .field("filter", &self.filter)
+            .field("wrap", &self.wrap)
+            .finish()
+    }
+}
+
+pub fn srgb_to_linear(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{color::Color, Point};
+
+//Anything that can color a point given in pattern space. Implement it
+// to plug your own procedural texture into a Pattern, which takes care
+// of the object and pattern transformations (see PatternType::custom_pattern).
+pub trait LocalPattern: fmt::Debug + Send + Sync {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color;
+}
+
+//A user defined pattern, shared between the clones of a material.
+#[derive(Clone)]
+pub struct CustomPattern(pub Arc<dyn LocalPattern>);
+
+impl CustomPattern {
+    pub fn new(pattern: impl LocalPattern + 'static) -> Self {
+        CustomPattern(Arc::new(pattern))
+    }
+
+    pub fn custom_at(&self, point: &Point<f64, 4>) -> Color {
+        self.0.local_pattern_at(point)
+    }
+}
+
+//There is no way to compare two trait objects, only to tell if they are the same one.
+impl PartialEq for CustomPattern {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for CustomPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{color::Color, Point};
+
+use super::{LocalPattern, Slot, SurfacePoint};
+
+//Picks between two patterns using the brightness of a third one:
+// pattern_a where the mask is black, pattern_b where it is white,
+// and a mix of both in between.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Masked {
+    pub mask: Slot,
+    pub pattern_a: Slot,
+    pub pattern_b: Slot,
+}
+
+impl Masked {
+    pub fn new(
+        mask: impl Into<Slot>,
+        pattern_a: impl Into<Slot>,
+        pattern_b: impl Into<Slot>,
+    ) -> Self {
+        Self {
+            mask: mask.into(),
+            pattern_a: pattern_a.into(),
+            pattern_b: pattern_b.into(),
+        }
+    }
+
+    pub fn masked_at(&self, point: &Point<f64, 4>) -> Color {
+        self.masked_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn masked_at_surface(&self, point: &Point<f64, 4>, surface: &SurfacePoint) -> Color {
+        let mask = self.mask.slot_at_surface(point, surface);
+        let weight = ((mask.red + mask.green + mask.blue) / 3.0).clamp(0.0, 1.0);
+
+        self.pattern_a.slot_at_surface(point, surface) * (1.0 - weight)
+            + self.pattern_b.slot_at_surface(point, surface) * weight
+    }
+}
+
+impl LocalPattern for Masked {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.masked_at(point)
+    }
+}
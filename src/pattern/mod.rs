@@ -1,3 +1,6 @@
+pub mod local_pattern;
+pub use local_pattern::CustomPattern;
+pub use local_pattern::LocalPattern;
 pub mod checker;
 pub use checker::Checker;
 pub mod ring;
@@ -9,3 +12,30 @@ pub use striped::Striped;
 pub mod pattern;
 pub use pattern::Pattern;
 pub use pattern::PatternType;
+pub mod uv_mapping;
+pub use uv_mapping::UvMapping;
+pub mod surface_point;
+pub use surface_point::SurfacePoint;
+pub mod uv_checker;
+pub use uv_checker::UvChecker;
+pub mod align_check;
+pub use align_check::AlignCheck;
+pub mod uv_pattern;
+pub use uv_pattern::UvPattern;
+pub mod texture_map;
+pub use texture_map::TextureMap;
+pub mod cube_map;
+pub use cube_map::CubeMap;
+pub use cube_map::CubeFace;
+pub mod image_texture;
+pub use image_texture::ImageTexture;
+pub mod perturbed;
+pub use perturbed::Perturbed;
+pub mod slot;
+pub use slot::Slot;
+pub mod blended;
+pub use blended::Blended;
+pub mod masked;
+pub use masked::Masked;
+pub mod additive;
+pub use additive::Additive;
@@ -1,8 +1,11 @@
-use crate::{color::Color, Matrix, Point, Striped};
+use crate::{color::Color, Matrix, Noise, Point, Striped};
 
-use super::{Checker, Gradient, Ring};
+use super::{
+    Additive, Blended, Checker, CubeMap, CustomPattern, Gradient, ImageTexture, LocalPattern,
+    Masked, Perturbed, Ring, Slot, SurfacePoint, TextureMap, UvMapping, UvPattern,
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Pattern {
     pub p_type: PatternType,
     pub transformation: Matrix<f64, 4, 4>,
@@ -27,6 +30,11 @@ impl Pattern {
         Pattern::new(p_type, iden, iden)
     }
 
+    pub fn with_transformation(mut self, transformation: Matrix<f64, 4, 4>) -> Self {
+        self.set_transformation(transformation);
+        self
+    }
+
     pub fn set_type(&mut self, p_type: PatternType) {
         self.p_type = p_type
     }
@@ -38,41 +46,133 @@ impl Pattern {
     pub fn pattern_at(&self, point: &Point<f64, 4>) -> Option<Color> {
         self.p_type.pattern_at(point)
     }
+
+    //Takes a point in the space of whatever the pattern is attached to
+    // (an object, or the pattern containing it) into pattern space.
+    pub fn pattern_at_object(&self, object_point: &Point<f64, 4>) -> Option<Color> {
+        self.pattern_on_surface(object_point, &SurfacePoint::none())
+    }
+
+    //pattern_at_object for a point on `surface`, which UvMapping::Surface
+    // textures anywhere in the pattern read their uv from.
+    pub fn pattern_on_surface(
+        &self,
+        object_point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Option<Color> {
+        let pattern_point = self.inverse * *object_point;
+        self.p_type.pattern_at_surface(&pattern_point, surface)
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum PatternType {
     Striped(Striped),
     Gradient(Gradient),
     Ring(Ring),
     Checker(Checker),
+    TextureMap(TextureMap),
+    CubeMap(Box<CubeMap>),
+    Perturbed(Perturbed),
+    Blended(Blended),
+    Masked(Masked),
+    Additive(Additive),
+    Custom(CustomPattern),
     None,
 }
 
 impl PatternType {
-    pub fn striped_pattern(color_a: Color, color_b: Color) -> PatternType {
+    pub fn striped_pattern(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> PatternType {
         PatternType::Striped(Striped::new(color_a, color_b))
     }
 
-    pub fn checker_pattern(color_a: Color, color_b: Color) -> PatternType {
+    pub fn checker_pattern(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> PatternType {
         PatternType::Checker(Checker::new(color_a, color_b))
     }
 
-    pub fn ring_pattern(color_a: Color, color_b: Color) -> PatternType {
+    pub fn ring_pattern(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> PatternType {
         PatternType::Ring(Ring::new(color_a, color_b))
     }
 
-    pub fn gradient_pattern(color_a: Color, color_b: Color) -> PatternType {
+    pub fn gradient_pattern(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> PatternType {
         PatternType::Gradient(Gradient::new(color_a, color_b))
     }
 
+    pub fn texture_map_pattern(uv_pattern: UvPattern, mapping: UvMapping) -> PatternType {
+        PatternType::TextureMap(TextureMap::new(uv_pattern, mapping))
+    }
+
+    //An image wrapped around the object, see ImageTexture::load.
+    pub fn image_pattern(texture: ImageTexture, mapping: UvMapping) -> PatternType {
+        PatternType::texture_map_pattern(UvPattern::Image(texture), mapping)
+    }
+
+    pub fn cube_map_pattern(
+        left: UvPattern,
+        front: UvPattern,
+        right: UvPattern,
+        back: UvPattern,
+        up: UvPattern,
+        down: UvPattern,
+    ) -> PatternType {
+        PatternType::CubeMap(Box::new(CubeMap::new(left, front, right, back, up, down)))
+    }
+
+    //`pattern` with its points jittered by fbm noise of the given seed.
+    pub fn perturbed_pattern(pattern: PatternType, scale: f64, seed: u64) -> PatternType {
+        PatternType::Perturbed(Perturbed::new(pattern, Noise::new(seed), scale, 4))
+    }
+
+    pub fn blended_pattern(pattern_a: impl Into<Slot>, pattern_b: impl Into<Slot>) -> PatternType {
+        PatternType::Blended(Blended::new(pattern_a, pattern_b))
+    }
+
+    pub fn masked_pattern(
+        mask: impl Into<Slot>,
+        pattern_a: impl Into<Slot>,
+        pattern_b: impl Into<Slot>,
+    ) -> PatternType {
+        PatternType::Masked(Masked::new(mask, pattern_a, pattern_b))
+    }
+
+    pub fn additive_pattern(pattern_a: impl Into<Slot>, pattern_b: impl Into<Slot>) -> PatternType {
+        PatternType::Additive(Additive::new(pattern_a, pattern_b))
+    }
+
+    //A pattern implemented outside of this crate.
+    pub fn custom_pattern(pattern: impl LocalPattern + 'static) -> PatternType {
+        PatternType::Custom(CustomPattern::new(pattern))
+    }
+
     pub fn pattern_at(&self, point: &Point<f64, 4>) -> Option<Color> {
+        self.pattern_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn pattern_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Option<Color> {
         match self {
-            PatternType::Striped(p) => Some(p.stripe_at(point)),
-            PatternType::Gradient(p) => Some(p.gradient_at(point)),
-            PatternType::Checker(p) => Some(p.checker_at(point)),
-            PatternType::Ring(p) => Some(p.ring_at(point)),
+            PatternType::Striped(p) => Some(p.stripe_at_surface(point, surface)),
+            PatternType::Gradient(p) => Some(p.gradient_at_surface(point, surface)),
+            PatternType::Checker(p) => Some(p.checker_at_surface(point, surface)),
+            PatternType::Ring(p) => Some(p.ring_at_surface(point, surface)),
+            PatternType::TextureMap(p) => Some(p.texture_map_at_surface(point, surface)),
+            PatternType::CubeMap(p) => Some(p.cube_map_at(point)),
+            PatternType::Perturbed(p) => p.perturbed_at_surface(point, surface),
+            PatternType::Blended(p) => Some(p.blended_at_surface(point, surface)),
+            PatternType::Masked(p) => Some(p.masked_at_surface(point, surface)),
+            PatternType::Additive(p) => Some(p.additive_at_surface(point, surface)),
+            PatternType::Custom(p) => Some(p.custom_at(point)),
             PatternType::None => None,
         }
     }
 }
+
+//PatternType::None has no color of its own, it shows up black.
+impl LocalPattern for PatternType {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.pattern_at(point).unwrap_or(Color::black())
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{color::Color, Noise, Point};
+
+use super::{LocalPattern, PatternType, SurfacePoint};
+
+//Moves the point around with noise before handing it to another
+// pattern, so straight stripes and rings turn into marble and wood grain.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Perturbed {
+    pub pattern: Box<PatternType>,
+    pub noise: Noise,
+    //how far, at most, a point may be moved
+    pub scale: f64,
+    pub octaves: u32,
+}
+
+impl Perturbed {
+    pub fn new(pattern: PatternType, noise: Noise, scale: f64, octaves: u32) -> Self {
+        Self {
+            pattern: Box::new(pattern),
+            noise,
+            scale,
+            octaves,
+        }
+    }
+
+    pub fn perturbed_at(&self, point: &Point<f64, 4>) -> Option<Color> {
+        self.perturbed_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn perturbed_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Option<Color> {
+        //three unrelated samples of the same noise, one for each axis
+        let sample = |offset: f64| {
+            let p = Point::new_point3D(point[0] + offset, point[1] + offset, point[2] + offset);
+            self.noise.fbm(&p, self.octaves) * self.scale
+        };
+
+        let jittered = Point::new_point3D(
+            point[0] + sample(0.0),
+            point[1] + sample(31.7),
+            point[2] + sample(73.3),
+        );
+
+        self.pattern.pattern_at_surface(&jittered, surface)
+    }
+}
+
+impl LocalPattern for Perturbed {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.perturbed_at(point).unwrap_or(Color::black())
+    }
+}
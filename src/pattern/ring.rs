@@ -1,25 +1,40 @@
 use crate::{color::Color, Point};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use super::{LocalPattern, Slot, SurfacePoint};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Ring {
-    pub color_a: Color,
-    pub color_b: Color,
+    pub color_a: Slot,
+    pub color_b: Slot,
 }
 
 impl Ring {
-    pub fn new(color_a: Color, color_b: Color) -> Self {
-        Self { color_a, color_b }
+    pub fn new(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> Self {
+        Self {
+            color_a: color_a.into(),
+            color_b: color_b.into(),
+        }
     }
 
     pub fn ring_at(&self, point: &Point<f64, 4>) -> Color {
+        self.ring_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn ring_at_surface(&self, point: &Point<f64, 4>, surface: &SurfacePoint) -> Color {
         let x = point.get(0).unwrap();
         let z = point.get(2).unwrap();
         let temp = f64::sqrt(x * x + z * z) as i64;
 
         if temp % 2 == 0 {
-            self.color_a.clone()
+            self.color_a.slot_at_surface(point, surface)
         } else {
-            self.color_b.clone()
+            self.color_b.slot_at_surface(point, surface)
         }
     }
 }
+
+impl LocalPattern for Ring {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.ring_at(point)
+    }
+}
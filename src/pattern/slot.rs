@@ -0,0 +1,45 @@
+use crate::{color::Color, Point};
+
+use super::{Pattern, PatternType, SurfacePoint};
+
+//What fills each part of a pattern: a flat color or a whole other
+// pattern, evaluated through its own transformation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Slot {
+    Color(Color),
+    Pattern(Box<Pattern>),
+}
+
+impl Slot {
+    //`point` is in the space of the pattern that owns the slot.
+    pub fn slot_at(&self, point: &Point<f64, 4>) -> Color {
+        self.slot_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn slot_at_surface(&self, point: &Point<f64, 4>, surface: &SurfacePoint) -> Color {
+        match self {
+            Slot::Color(color) => *color,
+            Slot::Pattern(pattern) => pattern
+                .pattern_on_surface(point, surface)
+                .unwrap_or(Color::black()),
+        }
+    }
+}
+
+impl From<Color> for Slot {
+    fn from(color: Color) -> Self {
+        Slot::Color(color)
+    }
+}
+
+impl From<Pattern> for Slot {
+    fn from(pattern: Pattern) -> Self {
+        Slot::Pattern(Box::new(pattern))
+    }
+}
+
+impl From<PatternType> for Slot {
+    fn from(p_type: PatternType) -> Self {
+        Slot::Pattern(Box::new(Pattern::with_type(p_type)))
+    }
+}
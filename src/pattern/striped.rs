@@ -1,24 +1,38 @@
 use crate::{color::Color, Point};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+use super::{LocalPattern, Slot, SurfacePoint};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Striped {
-    color_a: Color,
-    color_b: Color,
+    color_a: Slot,
+    color_b: Slot,
 }
 
 impl Striped {
-    pub fn new(color_a: Color, color_b: Color) -> Self {
-        Self { color_a, color_b }
+    pub fn new(color_a: impl Into<Slot>, color_b: impl Into<Slot>) -> Self {
+        Self {
+            color_a: color_a.into(),
+            color_b: color_b.into(),
+        }
     }
 
     pub fn stripe_at(&self, point: &Point<f64, 4>) -> Color {
+        self.stripe_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn stripe_at_surface(&self, point: &Point<f64, 4>, surface: &SurfacePoint) -> Color {
         let x = point.get(0).unwrap();
         let temp = x.floor() as isize;
         if temp % 2 == 0 {
-            self.color_a.clone()
+            self.color_a.slot_at_surface(point, surface)
         } else {
-            self.color_b.clone()
+            self.color_b.slot_at_surface(point, surface)
         }
     }
+}
 
+impl LocalPattern for Striped {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.stripe_at(point)
+    }
 }
@@ -0,0 +1,43 @@
+use std::cell::OnceCell;
+
+use crate::{Point, Shape};
+
+//Where on a shape a pattern is looked up, for UvMapping::Surface to read
+// the shape's own uv from. Finding the uv can mean searching a mesh, so it
+// is only worked out when a texture asks for it, once per lookup.
+pub struct SurfacePoint<'a> {
+    shape: Option<&'a Shape>,
+    object_point: Point<f64, 4>,
+    uv: OnceCell<(f64, f64)>,
+}
+
+impl<'a> SurfacePoint<'a> {
+    //`object_point` is in the space of `shape`.
+    pub fn new(shape: &'a Shape, object_point: Point<f64, 4>) -> Self {
+        Self {
+            shape: Some(shape),
+            object_point,
+            uv: OnceCell::new(),
+        }
+    }
+
+    //Not on any shape, surface mapped textures have no uv to read.
+    pub fn none() -> Self {
+        Self {
+            shape: None,
+            object_point: Point::new_point3D(0.0, 0.0, 0.0),
+            uv: OnceCell::new(),
+        }
+    }
+
+    pub fn uv(&self) -> Option<(f64, f64)> {
+        let shape = self.shape?;
+        Some(*self.uv.get_or_init(|| shape.local_uv(&self.object_point)))
+    }
+
+    //The uv somewhere else on the same shape, for normal maps that need to
+    // know how it changes around the point.
+    pub fn uv_at(&self, object_point: &Point<f64, 4>) -> Option<(f64, f64)> {
+        self.shape.map(|shape| shape.local_uv(object_point))
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{color::Color, Point};
+
+use super::{LocalPattern, SurfacePoint, UvMapping, UvPattern};
+
+//Wraps a 2D pattern around an object through a uv mapping.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextureMap {
+    pub uv_pattern: UvPattern,
+    pub mapping: UvMapping,
+}
+
+impl TextureMap {
+    pub fn new(uv_pattern: UvPattern, mapping: UvMapping) -> Self {
+        Self {
+            uv_pattern,
+            mapping,
+        }
+    }
+
+    //Surface mapped textures show up black, they need a SurfacePoint.
+    pub fn texture_map_at(&self, point: &Point<f64, 4>) -> Color {
+        self.texture_map_at_surface(point, &SurfacePoint::none())
+    }
+
+    pub(crate) fn texture_map_at_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Color {
+        match self.mapping.map_on_surface(point, surface) {
+            Some((u, v)) => self.uv_pattern.uv_pattern_at(u, v),
+            None => Color::black(),
+        }
+    }
+}
+
+impl LocalPattern for TextureMap {
+    fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+        self.texture_map_at(point)
+    }
+}
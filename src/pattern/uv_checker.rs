@@ -0,0 +1,32 @@
+use crate::color::Color;
+
+//Checkers in uv space, `width` squares along u and `height` along v.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UvChecker {
+    pub width: f64,
+    pub height: f64,
+    pub color_a: Color,
+    pub color_b: Color,
+}
+
+impl UvChecker {
+    pub fn new(width: f64, height: f64, color_a: Color, color_b: Color) -> Self {
+        Self {
+            width,
+            height,
+            color_a,
+            color_b,
+        }
+    }
+
+    pub fn uv_checker_at(&self, u: f64, v: f64) -> Color {
+        let u2 = (u * self.width).floor() as i64;
+        let v2 = (v * self.height).floor() as i64;
+
+        if (u2 + v2) % 2 == 0 {
+            self.color_a
+        } else {
+            self.color_b
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::f64::consts::PI;
+
+use crate::{Point, Vector};
+
+use super::SurfacePoint;
+
+//Turns a 3D point on the surface of an object into 2D (u, v) coordinates,
+// both in the [0, 1) range, so 2D patterns can be wrapped around it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvMapping {
+    Spherical,
+    Planar,
+    Cylindrical,
+    //The shape's own uv coordinates (see Object::uv_at), e.g. the texture
+    // coordinates of a mesh, read from the SurfacePoint the pattern is
+    // looked up on. They are used as they are: the pattern's transformation
+    // places it in 3D and has no effect on them.
+    Surface,
+}
+
+impl UvMapping {
+    //Panics for UvMapping::Surface, which can't tell anything from the
+    // point alone, use map_on_surface.
+    pub fn map(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => planar_map(point),
+            UvMapping::Cylindrical => cylindrical_map(point),
+            UvMapping::Surface => panic!("UvMapping::Surface needs a SurfacePoint"),
+        }
+    }
+
+    //None when the surface mapping has no shape to read from.
+    pub fn map_on_surface(
+        &self,
+        point: &Point<f64, 4>,
+        surface: &SurfacePoint,
+    ) -> Option<(f64, f64)> {
+        match self {
+            UvMapping::Surface => surface.uv(),
+            mapping => Some(mapping.map(point)),
+        }
+    }
+}
+
+//Longitude and latitude of a point on a sphere centered at the origin.
+pub fn spherical_map(point: &Point<f64, 4>) -> (f64, f64) {
+    let x = point[0];
+    let y = point[1];
+    let z = point[2];
+
+    // azimuthal angle, -pi < theta <= pi
+    let theta = f64::atan2(x, z);
+    let radius = Vector::new_vec3D(x, y, z).magnitude();
+    // polar angle, 0 <= phi <= pi
+    let phi = f64::acos(y / radius);
+
+    let raw_u = theta / (2.0 * PI);
+    // flip u so it increases counterclockwise when viewed from above
+    let u = 1.0 - (raw_u + 0.5);
+    // flip v so the north pole is at v = 1
+    let v = 1.0 - phi / PI;
+
+    (u, v)
+}
+
+//The xz plane, repeating every unit.
+pub fn planar_map(point: &Point<f64, 4>) -> (f64, f64) {
+    let u = point[0].rem_euclid(1.0);
+    let v = point[2].rem_euclid(1.0);
+
+    (u, v)
+}
+
+//A cylinder around the y axis, v repeating every unit of height.
+pub fn cylindrical_map(point: &Point<f64, 4>) -> (f64, f64) {
+    let theta = f64::atan2(point[0], point[2]);
+    let raw_u = theta / (2.0 * PI);
+    let u = 1.0 - (raw_u + 0.5);
+    let v = point[1].rem_euclid(1.0);
+
+    (u, v)
+}
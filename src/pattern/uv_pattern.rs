@@ -0,0 +1,41 @@
+use crate::color::Color;
+
+use super::{AlignCheck, ImageTexture, UvChecker};
+
+//Patterns defined over (u, v) instead of a 3D point.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UvPattern {
+    Checker(UvChecker),
+    AlignCheck(AlignCheck),
+    Image(ImageTexture),
+}
+
+impl UvPattern {
+    pub fn uv_checker_pattern(width: f64, height: f64, color_a: Color, color_b: Color) -> Self {
+        UvPattern::Checker(UvChecker::new(width, height, color_a, color_b))
+    }
+
+    pub fn align_check_pattern(
+        main: Color,
+        upper_left: Color,
+        upper_right: Color,
+        bottom_left: Color,
+        bottom_right: Color,
+    ) -> Self {
+        UvPattern::AlignCheck(AlignCheck::new(
+            main,
+            upper_left,
+            upper_right,
+            bottom_left,
+            bottom_right,
+        ))
+    }
+
+    pub fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        match self {
+            UvPattern::Checker(p) => p.uv_checker_at(u, v),
+            UvPattern::AlignCheck(p) => p.align_check_at(u, v),
+            UvPattern::Image(p) => p.uv_texture_at(u, v),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//How Canvas::write_png lays out the pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PngOptions {
+    //16 bits per channel instead of 8, which avoids banding in gradients.
+    pub sixteen_bit: bool,
+    //Writes the coverage of the canvas as an alpha channel, with the
+    // colors no longer premultiplied. Without it the colors are written
+    // as they are, which is the same as putting them over black.
+    pub alpha: bool,
+}
+
+impl PngOptions {
+    pub fn new() -> Self {
+        Self {
+            sixteen_bit: false,
+            alpha: true,
+        }
+    }
+
+    pub fn with_sixteen_bit(mut self, sixteen_bit: bool) -> Self {
+        self.sixteen_bit = sixteen_bit;
+        self
+    }
+
+    pub fn with_alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions::new()
+    }
+}
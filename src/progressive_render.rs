@@ -0,0 +1,129 @@
+use crate::color::Color;
+use crate::{Camera, Canvas, World};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+//Renders the image in passes of one ray per pixel and keeps the running
+// sums, so a preview is available after every pass and gets smoother the
+// longer it runs. Every pass shoots through a different point of the
+// pixels, the first one through their centers. The camera's `samples` is
+// not used.
+//
+//As an iterator it renders a pass on every `next` and yields the estimate
+// so far, until the render is cancelled.
+//
+//Setting the flag given to `with_cancel`, from any thread, stops the pass
+// in progress part-way. Its samples are dropped, so the estimate stays
+// that of the passes completed before.
+pub struct ProgressiveRender<'a> {
+    camera: &'a Camera,
+    world: &'a World,
+    //Sum of the colors seen through each pixel, misses add black.
+    sums: Vec<Color>,
+    //How many of the rays through each pixel hit something.
+    hits: Vec<usize>,
+    passes: usize,
+    cancel: Arc<AtomicBool>,
+}
+
+impl<'a> ProgressiveRender<'a> {
+    pub fn new(camera: &'a Camera, world: &'a World) -> Self {
+        let size = camera.hsize * camera.vsize;
+        Self {
+            camera,
+            world,
+            sums: vec![Color::black(); size],
+            hits: vec![0; size],
+            passes: 0,
+            cancel: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn passes(&self) -> usize {
+        self.passes
+    }
+
+    //Renders one more pass, false when it was cancelled.
+    pub fn pass(&mut self) -> bool {
+        let dx = offset(self.passes, 2);
+        let dy = offset(self.passes, 3);
+        let samples = self.camera.map_pixels(Some(&self.cancel), |px, py| {
+            self.camera.sample(self.world, px, py, dx, dy)
+        });
+        let Some(samples) = samples else {
+            return false;
+        };
+
+        for (px, py, sample) in samples {
+            if let Some(color) = sample {
+                let i = py * self.camera.hsize + px;
+                self.sums[i] = self.sums[i] + color;
+                self.hits[i] += 1;
+            }
+        }
+        self.passes += 1;
+        true
+    }
+
+    //The average of the passes so far, like Camera::render with the
+    // coverage in the alpha channel. All black and transparent before the
+    // first pass.
+    pub fn estimate(&self) -> Canvas {
+        let mut image = Canvas::new(self.camera.hsize, self.camera.vsize);
+        let passes = self.passes.max(1) as f64;
+
+        for py in 0..self.camera.vsize {
+            for px in 0..self.camera.hsize {
+                let i = py * self.camera.hsize + px;
+                image[py][px] = self.sums[i] * (1.0 / passes);
+                image.set_alpha(px, py, self.hits[i] as f64 / passes);
+            }
+        }
+        image
+    }
+
+    //Renders passes until `on_pass` returns false or the render is
+    // cancelled, handing it the estimate and the number of passes after
+    // each one. Returns the last estimate.
+    pub fn run(mut self, mut on_pass: impl FnMut(&Canvas, usize) -> bool) -> Canvas {
+        loop {
+            if !self.pass() {
+                return self.estimate();
+            }
+            let image = self.estimate();
+            if !on_pass(&image, self.passes) {
+                return image;
+            }
+        }
+    }
+}
+
+impl Iterator for ProgressiveRender<'_> {
+    type Item = Canvas;
+
+    fn next(&mut self) -> Option<Canvas> {
+        if !self.pass() {
+            return None;
+        }
+        Some(self.estimate())
+    }
+}
+
+//Where pass `index` hits the pixel along one axis: the Halton sequence in
+// `base`, shifted so the first pass lands in the middle.
+fn offset(index: usize, base: usize) -> f64 {
+    let mut result = 0.0;
+    let mut fraction = 1.0;
+    let mut index = index;
+    while index > 0 {
+        fraction /= base as f64;
+        result += fraction * (index % base) as f64;
+        index /= base;
+    }
+    (result + 0.5).fract()
+}
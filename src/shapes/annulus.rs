@@ -0,0 +1,60 @@
+use crate::{Point, Ray, Vector};
+
+use super::disk::{angle, plane_hit};
+use super::{Bounds, LocalShape};
+
+//A flat ring on the xz plane, centered at the origin and facing up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Annulus {
+    pub inner_radius: f64,
+    pub outer_radius: f64,
+}
+
+impl Annulus {
+    pub fn new(inner_radius: f64, outer_radius: f64) -> Self {
+        Annulus {
+            inner_radius,
+            outer_radius,
+        }
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let t = plane_hit(ray)?;
+        let point = ray.position(t);
+        let distance2 = point[0].powi(2) + point[2].powi(2);
+
+        if distance2 >= self.inner_radius.powi(2) && distance2 <= self.outer_radius.powi(2) {
+            Some(vec![t])
+        } else {
+            None
+        }
+    }
+}
+
+impl LocalShape for Annulus {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, _point: &Point<f64, 4>) -> Vector<f64, 4> {
+        Vector::new_vec3D(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(-self.outer_radius, 0.0, -self.outer_radius),
+            Point::new_point3D(self.outer_radius, 0.0, self.outer_radius),
+        )
+    }
+
+    //u goes around the center, v from the inner to the outer edge.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        let (x, z) = (point[0], point[2]);
+        let distance = (x * x + z * z).sqrt();
+
+        (
+            angle(x, z),
+            (distance - self.inner_radius) / (self.outer_radius - self.inner_radius),
+        )
+    }
+}
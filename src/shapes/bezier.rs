@@ -0,0 +1,130 @@
+use crate::{Point, Vector};
+
+use super::Mesh;
+
+//A bicubic Bézier surface. The 16 control points are stored row by row,
+// u going along a row and v from one row to the next.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BezierPatch {
+    pub control_points: [Point<f64, 4>; 16],
+}
+
+impl BezierPatch {
+    pub fn new(control_points: [Point<f64, 4>; 16]) -> Self {
+        Self { control_points }
+    }
+
+    pub fn point_at(&self, u: f64, v: f64) -> Point<f64, 4> {
+        let (bu, bv) = (bernstein(u), bernstein(v));
+        self.sum(|row, col| bv[row] * bu[col])
+    }
+
+    //Partial derivatives along u and along v.
+    pub fn tangents_at(&self, u: f64, v: f64) -> (Vector<f64, 4>, Vector<f64, 4>) {
+        let (bu, bv) = (bernstein(u), bernstein(v));
+        let (du, dv) = (bernstein_derivative(u), bernstein_derivative(v));
+        let origin = Point::new_point3D(0.0, 0.0, 0.0);
+
+        (
+            self.sum(|row, col| bv[row] * du[col]) - origin,
+            self.sum(|row, col| dv[row] * bu[col]) - origin,
+        )
+    }
+
+    //Where a whole edge collapses into a single point (the top of the
+    // teapot lid) the tangents vanish, so the normal is taken a tiny bit
+    // towards the middle of the patch instead.
+    pub fn normal_at(&self, u: f64, v: f64) -> Vector<f64, 4> {
+        let mut u = u;
+        let mut v = v;
+
+        for _ in 0..8 {
+            let (tangent_u, tangent_v) = self.tangents_at(u, v);
+            let normal = tangent_u | tangent_v;
+            if normal.magnitude() > 1.0e-12 {
+                return normal.normalize();
+            }
+            u += (0.5 - u) * 1.0e-3;
+            v += (0.5 - v) * 1.0e-3;
+        }
+
+        Vector::new_vec3D(0.0, 1.0, 0.0)
+    }
+
+    //Samples a (divisions + 1)^2 grid of points and normals into a smooth
+    // triangle mesh.
+    pub fn tessellate(&self, divisions: usize) -> Mesh {
+        tessellate(std::slice::from_ref(self), divisions)
+    }
+
+    fn sum(&self, weight: impl Fn(usize, usize) -> f64) -> Point<f64, 4> {
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        for row in 0..4 {
+            for col in 0..4 {
+                let w = weight(row, col);
+                let p = self.control_points[row * 4 + col];
+                x += w * p[0];
+                y += w * p[1];
+                z += w * p[2];
+            }
+        }
+
+        Point::new_point3D(x, y, z)
+    }
+}
+
+//All the patches in a single mesh, e.g. the 32 patches of the teapot.
+pub fn tessellate(patches: &[BezierPatch], divisions: usize) -> Mesh {
+    let divisions = divisions.max(1);
+    let side = divisions + 1;
+    let mut positions = Vec::with_capacity(patches.len() * side * side);
+    let mut normals = Vec::with_capacity(patches.len() * side * side);
+    let mut triangles = Vec::with_capacity(patches.len() * divisions * divisions * 2);
+
+    for patch in patches {
+        let first = positions.len();
+        for row in 0..side {
+            for col in 0..side {
+                let u = col as f64 / divisions as f64;
+                let v = row as f64 / divisions as f64;
+                positions.push(patch.point_at(u, v));
+                normals.push(patch.normal_at(u, v));
+            }
+        }
+
+        for row in 0..divisions {
+            for col in 0..divisions {
+                let a = first + row * side + col;
+                let (b, c, d) = (a + 1, a + side + 1, a + side);
+                for triangle in [[a, b, c], [a, c, d]] {
+                    if !is_degenerate(&positions, &triangle) {
+                        triangles.push(triangle);
+                    }
+                }
+            }
+        }
+    }
+
+    Mesh::new(positions, triangles).with_normals(normals)
+}
+
+//Collapsed edges leave triangles without any area.
+fn is_degenerate(positions: &[Point<f64, 4>], triangle: &[usize; 3]) -> bool {
+    let [a, b, c] = *triangle;
+    ((positions[b] - positions[a]) | (positions[c] - positions[a])).magnitude() < 1.0e-12
+}
+
+fn bernstein(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [s * s * s, 3.0 * t * s * s, 3.0 * t * t * s, t * t * t]
+}
+
+fn bernstein_derivative(t: f64) -> [f64; 4] {
+    let s = 1.0 - t;
+    [
+        -3.0 * s * s,
+        3.0 * s * s - 6.0 * t * s,
+        6.0 * t * s - 3.0 * t * t,
+        3.0 * t * t,
+    ]
+}
@@ -0,0 +1,130 @@
+use crate::{Matrix, Point, Ray};
+
+//Axis aligned box enclosing a shape. Unbounded shapes like planes use
+// infinite coordinates along the axes they extend over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Point<f64, 4>,
+    pub max: Point<f64, 4>,
+}
+
+impl Bounds {
+    pub fn new(min: Point<f64, 4>, max: Point<f64, 4>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn infinite() -> Self {
+        Self {
+            min: Point::new_point3D(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: Point::new_point3D(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    pub fn is_finite(&self) -> bool {
+        (0..3).all(|i| self.min[i].is_finite() && self.max[i].is_finite())
+    }
+
+    pub fn contains(&self, point: &Point<f64, 4>) -> bool {
+        (0..3).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    pub fn merge(&self, other: &Bounds) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(
+                self.min[0].min(other.min[0]),
+                self.min[1].min(other.min[1]),
+                self.min[2].min(other.min[2]),
+            ),
+            Point::new_point3D(
+                self.max[0].max(other.max[0]),
+                self.max[1].max(other.max[1]),
+                self.max[2].max(other.max[2]),
+            ),
+        )
+    }
+
+    //Grows the box by `amount` on every side.
+    pub fn expand(&self, amount: f64) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(
+                self.min[0] - amount,
+                self.min[1] - amount,
+                self.min[2] - amount,
+            ),
+            Point::new_point3D(
+                self.max[0] + amount,
+                self.max[1] + amount,
+                self.max[2] + amount,
+            ),
+        )
+    }
+
+    //Slab test: the range of t for which the ray is inside the box, if any.
+    pub fn intersect(&self, ray: &Ray) -> Option<(f64, f64)> {
+        let mut t_min = f64::NEG_INFINITY;
+        let mut t_max = f64::INFINITY;
+
+        for i in 0..3 {
+            let origin = ray.origin[i];
+            let direction = ray.direction[i];
+
+            if direction == 0.0 {
+                if origin < self.min[i] || origin > self.max[i] {
+                    return None;
+                }
+                continue;
+            }
+
+            let t1 = (self.min[i] - origin) / direction;
+            let t2 = (self.max[i] - origin) / direction;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+        }
+
+        if t_min > t_max {
+            None
+        } else {
+            Some((t_min, t_max))
+        }
+    }
+
+    //Bounds of the eight transformed corners. Rotating an infinite box
+    // would mix infinities of opposite signs, so it just stays infinite.
+    pub fn transform(&self, transformation: &Matrix<f64, 4, 4>) -> Bounds {
+        if !self.is_finite() {
+            return Bounds::infinite();
+        }
+
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+
+        for corner in 0..8 {
+            let x = if corner & 1 == 0 {
+                self.min[0]
+            } else {
+                self.max[0]
+            };
+            let y = if corner & 2 == 0 {
+                self.min[1]
+            } else {
+                self.max[1]
+            };
+            let z = if corner & 4 == 0 {
+                self.min[2]
+            } else {
+                self.max[2]
+            };
+            let point = *transformation * Point::new_point3D(x, y, z);
+
+            for i in 0..3 {
+                min[i] = min[i].min(point[i]);
+                max[i] = max[i].max(point[i]);
+            }
+        }
+
+        Bounds::new(
+            Point::new_point3D(min[0], min[1], min[2]),
+            Point::new_point3D(max[0], max[1], max[2]),
+        )
+    }
+}
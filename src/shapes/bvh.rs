@@ -0,0 +1,117 @@
+use crate::{Point, Ray};
+
+use super::Bounds;
+
+const LEAF_SIZE: usize = 4;
+
+#[derive(Clone, Debug)]
+enum Node {
+    Leaf { start: usize, end: usize },
+    Inner { left: usize, right: usize },
+}
+
+//Bounding volume hierarchy over a list of primitives, only known by
+// their bounds. Traversals hand back the indices of the primitives that
+// are worth testing.
+#[derive(Clone, Debug)]
+pub struct Bvh {
+    nodes: Vec<(Bounds, Node)>,
+    order: Vec<usize>,
+}
+
+impl Bvh {
+    pub fn build(primitives: &[Bounds]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            order: (0..primitives.len()).collect(),
+        };
+        if !primitives.is_empty() {
+            bvh.split(primitives, 0, primitives.len());
+        }
+        bvh
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        match self.nodes.first() {
+            Some((bounds, _)) => *bounds,
+            None => Bounds::new(
+                Point::new_point3D(0.0, 0.0, 0.0),
+                Point::new_point3D(0.0, 0.0, 0.0),
+            ),
+        }
+    }
+
+    //Sorts order[start..end] around the median centroid along the longest
+    // axis and recurses on both halves. Returns the index of the new node.
+    fn split(&mut self, primitives: &[Bounds], start: usize, end: usize) -> usize {
+        let bounds = self.order[start..end]
+            .iter()
+            .map(|i| primitives[*i])
+            .reduce(|a, b| a.merge(&b))
+            .unwrap();
+        let index = self.nodes.len();
+        self.nodes.push((bounds, Node::Leaf { start, end }));
+
+        if end - start <= LEAF_SIZE {
+            return index;
+        }
+
+        let centroid =
+            |i: usize, axis: usize| (primitives[i].min[axis] + primitives[i].max[axis]) / 2.0;
+        let extent = |axis: usize| bounds.max[axis] - bounds.min[axis];
+        let axis = (0..3).fold(0, |best, axis| {
+            if extent(axis) > extent(best) {
+                axis
+            } else {
+                best
+            }
+        });
+
+        let middle = (start + end) / 2;
+        self.order[start..end].select_nth_unstable_by(middle - start, |a, b| {
+            centroid(*a, axis).total_cmp(&centroid(*b, axis))
+        });
+
+        let left = self.split(primitives, start, middle);
+        let right = self.split(primitives, middle, end);
+        self.nodes[index].1 = Node::Inner { left, right };
+
+        index
+    }
+
+    pub fn traverse(&self, ray: &Ray, mut visit: impl FnMut(usize)) {
+        self.walk(|bounds| bounds.intersect(ray).is_some(), &mut visit);
+    }
+
+    //Every primitive whose bounds, grown by `tolerance`, hold the point.
+    pub fn query_point(&self, point: &Point<f64, 4>, tolerance: f64, mut visit: impl FnMut(usize)) {
+        self.walk(
+            |bounds| bounds.expand(tolerance).contains(point),
+            &mut visit,
+        );
+    }
+
+    fn walk(&self, enter: impl Fn(&Bounds) -> bool, visit: &mut impl FnMut(usize)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let (bounds, node) = &self.nodes[index];
+            if !enter(bounds) {
+                continue;
+            }
+
+            match node {
+                Node::Leaf { start, end } => {
+                    self.order[*start..*end].iter().for_each(|i| visit(*i))
+                }
+                Node::Inner { left, right } => {
+                    stack.push(*left);
+                    stack.push(*right);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::f64::consts::PI;
+
+use crate::{Point, Ray, Vector, EPSILON};
+
+use super::{Bounds, LocalShape};
+
+//A round piece of the xz plane, centered at the origin and facing up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Disk {
+    pub radius: f64,
+}
+
+impl Disk {
+    pub fn new(radius: f64) -> Self {
+        Disk { radius }
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let t = plane_hit(ray)?;
+        let point = ray.position(t);
+
+        if point[0].powi(2) + point[2].powi(2) <= self.radius.powi(2) {
+            Some(vec![t])
+        } else {
+            None
+        }
+    }
+}
+
+impl LocalShape for Disk {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, _point: &Point<f64, 4>) -> Vector<f64, 4> {
+        Vector::new_vec3D(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(-self.radius, 0.0, -self.radius),
+            Point::new_point3D(self.radius, 0.0, self.radius),
+        )
+    }
+
+    //u goes around the center, v from the center to the rim.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        let (x, z) = (point[0], point[2]);
+        (angle(x, z), (x * x + z * z).sqrt() / self.radius)
+    }
+}
+
+//Where the ray crosses y = 0, shared by every flat shape.
+pub(crate) fn plane_hit(ray: &Ray) -> Option<f64> {
+    if ray.direction[1].abs() < EPSILON {
+        None
+    } else {
+        Some(-ray.origin[1] / ray.direction[1])
+    }
+}
+
+//Angle around the y axis, in [0, 1).
+pub(crate) fn angle(x: f64, z: f64) -> f64 {
+    (f64::atan2(x, z) / (2.0 * PI)).rem_euclid(1.0)
+}
@@ -0,0 +1,349 @@
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use image::error::{ParameterError, ParameterErrorKind};
+use image::{DynamicImage, ImageError};
+
+use crate::{Point, Ray, Vector};
+
+use super::triangle::intersect_triangle;
+use super::{Bounds, LocalShape};
+
+//Two triangles sharing the same edge can both report the same hit.
+const SAME_HIT: f64 = 1.0e-9;
+
+//Terrain over the square from (-1, -1) to (1, 1) on the xz plane, with
+// one height sample per grid vertex. Each cell is split in two triangles,
+// shaded with normals interpolated from the neighbouring samples. Scale
+// the object to give it its real size.
+#[derive(Clone, PartialEq)]
+pub struct Heightfield {
+    width: usize,
+    depth: usize,
+    //Row by row, `depth` rows of `width` samples, the first row at z = -1.
+    heights: Arc<[f64]>,
+    min_height: f64,
+    max_height: f64,
+}
+
+impl Heightfield {
+    pub fn new(width: usize, depth: usize, heights: Vec<f64>) -> Self {
+        assert!(
+            width >= 2 && depth >= 2,
+            "a heightfield needs at least 2x2 samples"
+        );
+        assert_eq!(width * depth, heights.len());
+
+        let min_height = heights.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max_height = heights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        Self {
+            width,
+            depth,
+            heights: heights.into(),
+            min_height,
+            max_height,
+        }
+    }
+
+    //Samples `height(u, v)` on a width x depth grid, u and v in [0, 1]
+    // going along x and z.
+    pub fn from_fn(width: usize, depth: usize, height: impl Fn(f64, f64) -> f64) -> Self {
+        let mut heights = Vec::with_capacity(width * depth);
+        for j in 0..depth {
+            for i in 0..width {
+                let u = i as f64 / (width - 1) as f64;
+                let v = j as f64 / (depth - 1) as f64;
+                heights.push(height(u, v));
+            }
+        }
+
+        Heightfield::new(width, depth, heights)
+    }
+
+    //Loads a grayscale image, black being height 0 and white height 1.
+    // The top row of the image ends up at z = -1.
+    pub fn load<P: AsRef<Path>>(path: P) -> image::ImageResult<Self> {
+        Heightfield::from_image(&image::open(path)?)
+    }
+
+    //Fails for images smaller than 2x2 pixels.
+    pub fn from_image(image: &DynamicImage) -> image::ImageResult<Self> {
+        if image.width() < 2 || image.height() < 2 {
+            return Err(ImageError::Parameter(ParameterError::from_kind(
+                ParameterErrorKind::Generic(
+                    "a heightfield image needs at least 2x2 pixels".to_string(),
+                ),
+            )));
+        }
+        let luma = image.to_luma16();
+        let heights = luma
+            .pixels()
+            .map(|p| p[0] as f64 / u16::MAX as f64)
+            .collect();
+
+        Ok(Heightfield::new(
+            luma.width() as usize,
+            luma.height() as usize,
+            heights,
+        ))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn sample(&self, i: usize, j: usize) -> f64 {
+        self.heights[j * self.width + i]
+    }
+
+    //Height of the surface above (x, z), or None outside of the grid.
+    pub fn height_at(&self, x: f64, z: f64) -> Option<f64> {
+        let (i, j, fx, fz) = self.locate(x, z)?;
+        let [p0, p1, p2] = self.triangle(i, j, fx, fz);
+        let (w0, w1, w2) = barycentric(&p0, &p1, &p2, x, z);
+
+        Some(w0 * p0[1] + w1 * p1[1] + w2 * p2[1])
+    }
+
+    //Walks the cells under the ray in order (Amanatides and Woo), only
+    // testing the triangles of cells whose height range the ray overlaps.
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let (t_start, t_end) = self.bounds().intersect(ray)?;
+        let start = ray.position(t_start);
+        let (dx, dz) = (ray.direction[0], ray.direction[2]);
+        let cell_x = 2.0 / (self.width - 1) as f64;
+        let cell_z = 2.0 / (self.depth - 1) as f64;
+
+        let mut i = self.cell_index(start[0], self.width);
+        let mut j = self.cell_index(start[2], self.depth);
+
+        let step_i: isize = if dx > 0.0 { 1 } else { -1 };
+        let step_j: isize = if dz > 0.0 { 1 } else { -1 };
+        let delta_x = (cell_x / dx).abs();
+        let delta_z = (cell_z / dz).abs();
+        let next_x = -1.0 + (i as f64 + if dx > 0.0 { 1.0 } else { 0.0 }) * cell_x;
+        let next_z = -1.0 + (j as f64 + if dz > 0.0 { 1.0 } else { 0.0 }) * cell_z;
+        let mut t_max_x = if dx == 0.0 {
+            f64::INFINITY
+        } else {
+            (next_x - ray.origin[0]) / dx
+        };
+        let mut t_max_z = if dz == 0.0 {
+            f64::INFINITY
+        } else {
+            (next_z - ray.origin[2]) / dz
+        };
+
+        let mut t_cell_start = t_start;
+        let mut hits: Vec<f64> = Vec::new();
+
+        loop {
+            let t_cell_end = t_max_x.min(t_max_z).min(t_end);
+            self.intersect_cell(ray, i, j, t_cell_start, t_cell_end, &mut hits);
+
+            if t_cell_end >= t_end {
+                break;
+            }
+
+            if t_max_x < t_max_z {
+                i = match i.checked_add_signed(step_i) {
+                    Some(i) if i < self.width - 1 => i,
+                    _ => break,
+                };
+                t_max_x += delta_x;
+            } else {
+                j = match j.checked_add_signed(step_j) {
+                    Some(j) if j < self.depth - 1 => j,
+                    _ => break,
+                };
+                t_max_z += delta_z;
+            }
+            t_cell_start = t_cell_end;
+        }
+
+        if hits.is_empty() {
+            None
+        } else {
+            Some(hits)
+        }
+    }
+
+    //Normals of the triangle's corners, averaged with barycentric weights.
+    pub fn normal_at(&self, object_point: &Point<f64, 4>) -> Vector<f64, 4> {
+        let (x, z) = (object_point[0], object_point[2]);
+        let (i, j, fx, fz) = match self.locate(x, z) {
+            Some(cell) => cell,
+            None => return Vector::new_vec3D(0.0, 1.0, 0.0),
+        };
+        let [p0, p1, p2] = self.triangle(i, j, fx, fz);
+        let (w0, w1, w2) = barycentric(&p0, &p1, &p2, x, z);
+
+        let vertex = |p: &Point<f64, 4>| self.vertex_normal(self.grid_x(p[0]), self.grid_z(p[2]));
+
+        (vertex(&p0) * w0 + vertex(&p1) * w1 + vertex(&p2) * w2).normalize()
+    }
+
+    //Central differences over the neighbouring samples.
+    fn vertex_normal(&self, i: usize, j: usize) -> Vector<f64, 4> {
+        let left = i.saturating_sub(1);
+        let right = (i + 1).min(self.width - 1);
+        let back = j.saturating_sub(1);
+        let front = (j + 1).min(self.depth - 1);
+
+        let dh_dx = (self.sample(right, j) - self.sample(left, j)) / (self.x(right) - self.x(left));
+        let dh_dz = (self.sample(i, front) - self.sample(i, back)) / (self.z(front) - self.z(back));
+
+        Vector::new_vec3D(-dh_dx, 1.0, -dh_dz).normalize()
+    }
+
+    fn intersect_cell(
+        &self,
+        ray: &Ray,
+        i: usize,
+        j: usize,
+        t_cell_start: f64,
+        t_cell_end: f64,
+        hits: &mut Vec<f64>,
+    ) {
+        let corners = [
+            self.sample(i, j),
+            self.sample(i + 1, j),
+            self.sample(i, j + 1),
+            self.sample(i + 1, j + 1),
+        ];
+        let lowest = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+        let highest = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let y_start = ray.origin[1] + ray.direction[1] * t_cell_start;
+        let y_end = ray.origin[1] + ray.direction[1] * t_cell_end;
+        if y_start.min(y_end) > highest || y_start.max(y_end) < lowest {
+            return;
+        }
+
+        let p00 = self.vertex(i, j);
+        let p10 = self.vertex(i + 1, j);
+        let p01 = self.vertex(i, j + 1);
+        let p11 = self.vertex(i + 1, j + 1);
+
+        let mut cell_hits: Vec<f64> = [(p00, p10, p11), (p00, p11, p01)]
+            .iter()
+            .filter_map(|(a, b, c)| intersect_triangle(ray, a, b, c).map(|(t, _, _)| t))
+            .filter(|t| *t >= t_cell_start - SAME_HIT && *t <= t_cell_end + SAME_HIT)
+            .collect();
+        cell_hits.sort_by(|a, b| a.total_cmp(b));
+
+        for t in cell_hits {
+            if hits.last().is_none_or(|last| (t - last).abs() > SAME_HIT) {
+                hits.push(t);
+            }
+        }
+    }
+
+    fn x(&self, i: usize) -> f64 {
+        -1.0 + 2.0 * i as f64 / (self.width - 1) as f64
+    }
+
+    fn z(&self, j: usize) -> f64 {
+        -1.0 + 2.0 * j as f64 / (self.depth - 1) as f64
+    }
+
+    fn grid_x(&self, x: f64) -> usize {
+        ((x + 1.0) / 2.0 * (self.width - 1) as f64).round() as usize
+    }
+
+    fn grid_z(&self, z: f64) -> usize {
+        ((z + 1.0) / 2.0 * (self.depth - 1) as f64).round() as usize
+    }
+
+    fn vertex(&self, i: usize, j: usize) -> Point<f64, 4> {
+        Point::new_point3D(self.x(i), self.sample(i, j), self.z(j))
+    }
+
+    fn cell_index(&self, coordinate: f64, samples: usize) -> usize {
+        let cell = ((coordinate + 1.0) / 2.0 * (samples - 1) as f64).floor();
+        (cell.max(0.0) as usize).min(samples - 2)
+    }
+
+    //The cell under (x, z) and the position inside of it, both in [0, 1].
+    fn locate(&self, x: f64, z: f64) -> Option<(usize, usize, f64, f64)> {
+        if !(-1.0..=1.0).contains(&x) || !(-1.0..=1.0).contains(&z) {
+            return None;
+        }
+
+        let i = self.cell_index(x, self.width);
+        let j = self.cell_index(z, self.depth);
+        let fx = (x + 1.0) / 2.0 * (self.width - 1) as f64 - i as f64;
+        let fz = (z + 1.0) / 2.0 * (self.depth - 1) as f64 - j as f64;
+
+        Some((i, j, fx, fz))
+    }
+
+    //The cell's diagonal goes from (i, j) to (i + 1, j + 1).
+    fn triangle(&self, i: usize, j: usize, fx: f64, fz: f64) -> [Point<f64, 4>; 3] {
+        if fx >= fz {
+            [
+                self.vertex(i, j),
+                self.vertex(i + 1, j),
+                self.vertex(i + 1, j + 1),
+            ]
+        } else {
+            [
+                self.vertex(i, j),
+                self.vertex(i + 1, j + 1),
+                self.vertex(i, j + 1),
+            ]
+        }
+    }
+}
+
+impl fmt::Debug for Heightfield {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Heightfield")
+            .field("width", &self.width)
+            .field("depth", &self.depth)
+            .field("min_height", &self.min_height)
+            .field("max_height", &self.max_height)
+            .finish()
+    }
+}
+
+impl LocalShape for Heightfield {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        self.normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(-1.0, self.min_height, -1.0),
+            Point::new_point3D(1.0, self.max_height, 1.0),
+        )
+    }
+
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        ((point[0] + 1.0) / 2.0, (point[2] + 1.0) / 2.0)
+    }
+}
+
+//Weights of (x, z) relative to the triangle, seen from above.
+fn barycentric(
+    p0: &Point<f64, 4>,
+    p1: &Point<f64, 4>,
+    p2: &Point<f64, 4>,
+    x: f64,
+    z: f64,
+) -> (f64, f64, f64) {
+    let area = (p1[0] - p0[0]) * (p2[2] - p0[2]) - (p2[0] - p0[0]) * (p1[2] - p0[2]);
+    let w1 = ((x - p0[0]) * (p2[2] - p0[2]) - (p2[0] - p0[0]) * (z - p0[2])) / area;
+    let w2 = ((p1[0] - p0[0]) * (z - p0[2]) - (x - p0[0]) * (p1[2] - p0[2])) / area;
+
+    (1.0 - w1 - w2, w1, w2)
+}
@@ -0,0 +1,44 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Point, Ray, Vector};
+
+use super::Bounds;
+
+//Everything the tracer needs from a primitive, in object space. Implement
+// it to add your own shape, Object takes care of the transformations
+// (see Shape::custom_shape).
+pub trait LocalShape: fmt::Debug + Send + Sync {
+    //Every t where the ray crosses the surface, or None if it misses.
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>>;
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4>;
+
+    fn bounds(&self) -> Bounds;
+
+    //(u, v) coordinates of a point on the surface, both in [0, 1).
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64);
+}
+
+//A user defined shape, shared between the clones of an object.
+#[derive(Clone)]
+pub struct CustomShape(pub Arc<dyn LocalShape>);
+
+impl CustomShape {
+    pub fn new(shape: impl LocalShape + 'static) -> Self {
+        CustomShape(Arc::new(shape))
+    }
+}
+
+//There is no way to compare two trait objects, only to tell if they are the same one.
+impl PartialEq for CustomShape {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for CustomShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::color::Color;
+use crate::{Point, Ray, Vector};
+
+use super::triangle::{barycentric, intersect_triangle};
+use super::{Bounds, Bvh, LocalShape};
+
+//Two triangles sharing the same edge can both report the same hit.
+const SAME_HIT: f64 = 1.0e-9;
+//How far off the surface a point can be and still be found on it.
+const ON_SURFACE: f64 = 1.0e-6;
+
+//Triangles indexing into shared vertex arrays, kept behind Arcs so
+// cloning an object does not copy the geometry. Per-vertex normals give a
+// smooth surface, per-vertex colors and uvs are interpolated the same way.
+#[derive(Clone)]
+pub struct Mesh {
+    positions: Arc<[Point<f64, 4>]>,
+    triangles: Arc<[[usize; 3]]>,
+    normals: Option<Arc<[Vector<f64, 4>]>>,
+    colors: Option<Arc<[Color]>>,
+    uvs: Option<Arc<[(f64, f64)]>>,
+    bvh: Arc<Bvh>,
+}
+
+impl Mesh {
+    pub fn new(positions: Vec<Point<f64, 4>>, triangles: Vec<[usize; 3]>) -> Self {
+        assert!(
+            triangles.iter().flatten().all(|i| *i < positions.len()),
+            "triangle index out of range"
+        );
+
+        let boxes: Vec<Bounds> = triangles
+            .iter()
+            .map(|[a, b, c]| {
+                Bounds::new(positions[*a], positions[*a])
+                    .merge(&Bounds::new(positions[*b], positions[*b]))
+                    .merge(&Bounds::new(positions[*c], positions[*c]))
+            })
+            .collect();
+
+        Self {
+            bvh: Arc::new(Bvh::build(&boxes)),
+            positions: positions.into(),
+            triangles: triangles.into(),
+            normals: None,
+            colors: None,
+            uvs: None,
+        }
+    }
+
+    pub fn with_normals(mut self, normals: Vec<Vector<f64, 4>>) -> Self {
+        assert_eq!(self.positions.len(), normals.len());
+        self.normals = Some(normals.into());
+        self
+    }
+
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        assert_eq!(self.positions.len(), colors.len());
+        self.colors = Some(colors.into());
+        self
+    }
+
+    pub fn with_uvs(mut self, uvs: Vec<(f64, f64)>) -> Self {
+        assert_eq!(self.positions.len(), uvs.len());
+        self.uvs = Some(uvs.into());
+        self
+    }
+
+    //Smooth normals from the area weighted face normals around each vertex.
+    pub fn with_computed_normals(self) -> Self {
+        let mut normals = vec![Vector::new_vec3D(0.0, 0.0, 0.0); self.positions.len()];
+        for triangle in self.triangles.iter() {
+            let face = self.face_normal(triangle);
+            for i in triangle {
+                normals[*i] = normals[*i] + face;
+            }
+        }
+        let normals = normals
+            .into_iter()
+            .map(|n| {
+                if n.magnitude() > 0.0 {
+                    n.normalize()
+                } else {
+                    n
+                }
+            })
+            .collect();
+
+        self.with_normals(normals)
+    }
+
+    pub fn positions(&self) -> &[Point<f64, 4>] {
+        &self.positions
+    }
+
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    pub fn normals(&self) -> Option<&[Vector<f64, 4>]> {
+        self.normals.as_deref()
+    }
+
+    pub fn colors(&self) -> Option<&[Color]> {
+        self.colors.as_deref()
+    }
+
+    pub fn uvs(&self) -> Option<&[(f64, f64)]> {
+        self.uvs.as_deref()
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let mut hits = Vec::new();
+        self.bvh.traverse(ray, |i| {
+            let [a, b, c] = self.triangles[i];
+            let (p0, p1, p2) = (&self.positions[a], &self.positions[b], &self.positions[c]);
+            if let Some((t, _, _)) = intersect_triangle(ray, p0, p1, p2) {
+                hits.push(t);
+            }
+        });
+
+        hits.sort_by(|a, b| a.total_cmp(b));
+        hits.dedup_by(|a, b| (*a - *b).abs() < SAME_HIT);
+
+        if hits.is_empty() {
+            None
+        } else {
+            Some(hits)
+        }
+    }
+
+    //Only a point is known when shading, so the triangle it lies on is
+    // looked up again through the hierarchy.
+    pub fn normal_at(&self, object_point: &Point<f64, 4>) -> Vector<f64, 4> {
+        match (self.locate(object_point), &self.normals) {
+            (Some((triangle, weights)), Some(normals)) => {
+                let [a, b, c] = self.triangles[triangle];
+                (normals[a] * weights[0] + normals[b] * weights[1] + normals[c] * weights[2])
+                    .normalize()
+            }
+            (Some((triangle, _)), None) => self.face_normal(&self.triangles[triangle]).normalize(),
+            (None, _) => Vector::new_vec3D(0.0, 1.0, 0.0),
+        }
+    }
+
+    //Interpolated vertex color, if the mesh has any.
+    pub fn color_at(&self, object_point: &Point<f64, 4>) -> Option<Color> {
+        let colors = self.colors.as_ref()?;
+        let (triangle, weights) = self.locate(object_point)?;
+        let [a, b, c] = self.triangles[triangle];
+
+        Some(colors[a] * weights[0] + colors[b] * weights[1] + colors[c] * weights[2])
+    }
+
+    pub fn uv_at(&self, object_point: &Point<f64, 4>) -> Option<(f64, f64)> {
+        let uvs = self.uvs.as_ref()?;
+        let (triangle, weights) = self.locate(object_point)?;
+        let [a, b, c] = self.triangles[triangle];
+
+        Some((
+            uvs[a].0 * weights[0] + uvs[b].0 * weights[1] + uvs[c].0 * weights[2],
+            uvs[a].1 * weights[0] + uvs[b].1 * weights[1] + uvs[c].1 * weights[2],
+        ))
+    }
+
+    //The triangle closest to the point, with the point's barycentric weights.
+    fn locate(&self, point: &Point<f64, 4>) -> Option<(usize, [f64; 3])> {
+        let mut best: Option<(f64, usize, [f64; 3])> = None;
+
+        self.bvh.query_point(point, ON_SURFACE, |i| {
+            let [a, b, c] = self.triangles[i];
+            let (p0, p1, p2) = (&self.positions[a], &self.positions[b], &self.positions[c]);
+            if let Some((weights, distance)) = barycentric(point, p0, p1, p2) {
+                let outside: f64 = weights.iter().map(|w| w.min(0.0).abs()).sum();
+                let score = distance + outside;
+                if best.is_none_or(|(best_score, _, _)| score < best_score) {
+                    best = Some((score, i, weights));
+                }
+            }
+        });
+
+        best.map(|(_, i, weights)| (i, weights.map(|w| w.clamp(0.0, 1.0))))
+    }
+
+    fn face_normal(&self, triangle: &[usize; 3]) -> Vector<f64, 4> {
+        let [a, b, c] = *triangle;
+        (self.positions[b] - self.positions[a]) | (self.positions[c] - self.positions[a])
+    }
+}
+
+//Two meshes are only equal if they share the same geometry.
+impl PartialEq for Mesh {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.bvh, &other.bvh)
+            && self.normals.as_ref().map(Arc::as_ptr) == other.normals.as_ref().map(Arc::as_ptr)
+            && self.colors.as_ref().map(Arc::as_ptr) == other.colors.as_ref().map(Arc::as_ptr)
+            && self.uvs.as_ref().map(Arc::as_ptr) == other.uvs.as_ref().map(Arc::as_ptr)
+    }
+}
+
+impl fmt::Debug for Mesh {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mesh")
+            .field("vertices", &self.positions.len())
+            .field("triangles", &self.triangles.len())
+            .field("normals", &self.normals.is_some())
+            .field("colors", &self.colors.is_some())
+            .field("uvs", &self.uvs.is_some())
+            .finish()
+    }
+}
+
+impl LocalShape for Mesh {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        self.normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.bvh.bounds()
+    }
+
+    //Falls back to a spherical map for meshes without texture coordinates.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        self.uv_at(point)
+            .unwrap_or_else(|| crate::pattern::uv_mapping::spherical_map(point))
+    }
+}
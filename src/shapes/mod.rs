@@ -2,5 +2,31 @@ pub mod sphere;
 pub use sphere::Sphere;
 pub mod plane;
 pub use plane::Plane;
+pub mod disk;
+pub use disk::Disk;
+pub mod rectangle;
+pub use rectangle::Rectangle;
+pub mod annulus;
+pub use annulus::Annulus;
+pub mod torus;
+pub use torus::Torus;
+pub mod sdf;
+pub use sdf::Sdf;
+pub mod sdf_shape;
+pub use sdf_shape::SdfShape;
+pub mod heightfield;
+pub use heightfield::Heightfield;
 pub mod shape;
 pub use shape::Shape;
+pub mod bounds;
+pub use bounds::Bounds;
+pub mod local_shape;
+pub use local_shape::CustomShape;
+pub use local_shape::LocalShape;
+pub mod triangle;
+pub mod bvh;
+pub use bvh::Bvh;
+pub mod mesh;
+pub use mesh::Mesh;
+pub mod bezier;
+pub use bezier::BezierPatch;
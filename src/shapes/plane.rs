@@ -1,5 +1,8 @@
+use crate::pattern::uv_mapping::planar_map;
 use crate::{Point, Ray, Vector, EPSILON, ApproximateEq};
 
+use super::{Bounds, LocalShape};
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Plane {}
 
@@ -25,3 +28,24 @@ impl Plane {
         }
     }
 }
+
+impl LocalShape for Plane {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        Plane::intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        Plane::normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new_point3D(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        planar_map(point)
+    }
+}
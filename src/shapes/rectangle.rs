@@ -0,0 +1,58 @@
+use crate::{Point, Ray, Vector};
+
+use super::disk::plane_hit;
+use super::{Bounds, LocalShape};
+
+//An axis aligned rectangle on the xz plane, centered at the origin and
+// facing up, going from -half_width to half_width along x and from
+// -half_depth to half_depth along z.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rectangle {
+    pub half_width: f64,
+    pub half_depth: f64,
+}
+
+impl Rectangle {
+    pub fn new(half_width: f64, half_depth: f64) -> Self {
+        Rectangle {
+            half_width,
+            half_depth,
+        }
+    }
+
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let t = plane_hit(ray)?;
+        let point = ray.position(t);
+
+        if point[0].abs() <= self.half_width && point[2].abs() <= self.half_depth {
+            Some(vec![t])
+        } else {
+            None
+        }
+    }
+}
+
+impl LocalShape for Rectangle {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, _point: &Point<f64, 4>) -> Vector<f64, 4> {
+        Vector::new_vec3D(0.0, 1.0, 0.0)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(-self.half_width, 0.0, -self.half_depth),
+            Point::new_point3D(self.half_width, 0.0, self.half_depth),
+        )
+    }
+
+    //The whole rectangle covers the uv square once.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        (
+            (point[0] + self.half_width) / (2.0 * self.half_width),
+            (point[2] + self.half_depth) / (2.0 * self.half_depth),
+        )
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{Point, Vector};
+
+use super::Bounds;
+
+//A signed distance function: how far a point is from the surface,
+// negative inside. Primitives are centered at the origin and can be
+// combined into a tree, `k` being how far the smooth operators blend.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sdf {
+    Sphere {
+        radius: f64,
+    },
+    //Half the size of the box along each axis.
+    Cuboid {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Torus {
+        major_radius: f64,
+        minor_radius: f64,
+    },
+    //Along the y axis, from -half_height to half_height.
+    Cylinder {
+        radius: f64,
+        half_height: f64,
+    },
+    Capsule {
+        radius: f64,
+        half_height: f64,
+    },
+    Mandelbulb {
+        power: f64,
+        iterations: usize,
+    },
+    Translated(Box<Sdf>, Vector<f64, 4>),
+    //Only uniform scaling keeps the function a true distance.
+    Scaled(Box<Sdf>, f64),
+    Union(Box<Sdf>, Box<Sdf>),
+    Subtraction(Box<Sdf>, Box<Sdf>),
+    Intersection(Box<Sdf>, Box<Sdf>),
+    SmoothUnion(Box<Sdf>, Box<Sdf>, f64),
+    SmoothSubtraction(Box<Sdf>, Box<Sdf>, f64),
+    SmoothIntersection(Box<Sdf>, Box<Sdf>, f64),
+}
+
+impl Sdf {
+    pub fn sphere(radius: f64) -> Self {
+        Sdf::Sphere { radius }
+    }
+
+    pub fn cuboid(x: f64, y: f64, z: f64) -> Self {
+        Sdf::Cuboid { x, y, z }
+    }
+
+    pub fn torus(major_radius: f64, minor_radius: f64) -> Self {
+        Sdf::Torus {
+            major_radius,
+            minor_radius,
+        }
+    }
+
+    pub fn cylinder(radius: f64, half_height: f64) -> Self {
+        Sdf::Cylinder {
+            radius,
+            half_height,
+        }
+    }
+
+    pub fn capsule(radius: f64, half_height: f64) -> Self {
+        Sdf::Capsule {
+            radius,
+            half_height,
+        }
+    }
+
+    pub fn mandelbulb(power: f64, iterations: usize) -> Self {
+        Sdf::Mandelbulb { power, iterations }
+    }
+
+    pub fn translated(self, x: f64, y: f64, z: f64) -> Self {
+        Sdf::Translated(Box::new(self), Vector::new_vec3D(x, y, z))
+    }
+
+    //`factor` has to be positive, a negative one would turn the shape
+    // inside out.
+    pub fn scaled(self, factor: f64) -> Self {
+        assert!(
+            factor > 0.0,
+            "an sdf can only be scaled by a positive factor"
+        );
+        Sdf::Scaled(Box::new(self), factor)
+    }
+
+    pub fn union(self, other: Sdf) -> Self {
+        Sdf::Union(Box::new(self), Box::new(other))
+    }
+
+    //Carves `other` out of self.
+    pub fn subtraction(self, other: Sdf) -> Self {
+        Sdf::Subtraction(Box::new(self), Box::new(other))
+    }
+
+    pub fn intersection(self, other: Sdf) -> Self {
+        Sdf::Intersection(Box::new(self), Box::new(other))
+    }
+
+    pub fn smooth_union(self, other: Sdf, k: f64) -> Self {
+        Sdf::SmoothUnion(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn smooth_subtraction(self, other: Sdf, k: f64) -> Self {
+        Sdf::SmoothSubtraction(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn smooth_intersection(self, other: Sdf, k: f64) -> Self {
+        Sdf::SmoothIntersection(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn distance(&self, point: &Point<f64, 4>) -> f64 {
+        let (x, y, z) = (point[0], point[1], point[2]);
+
+        match self {
+            Sdf::Sphere { radius } => length(x, y, z) - radius,
+            Sdf::Cuboid {
+                x: half_x,
+                y: half_y,
+                z: half_z,
+            } => {
+                let qx = x.abs() - half_x;
+                let qy = y.abs() - half_y;
+                let qz = z.abs() - half_z;
+                let outside = length(qx.max(0.0), qy.max(0.0), qz.max(0.0));
+                let inside = qx.max(qy).max(qz).min(0.0);
+                outside + inside
+            }
+            Sdf::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let from_ring = (x * x + z * z).sqrt() - major_radius;
+                (from_ring * from_ring + y * y).sqrt() - minor_radius
+            }
+            Sdf::Cylinder {
+                radius,
+                half_height,
+            } => {
+                let dx = (x * x + z * z).sqrt() - radius;
+                let dy = y.abs() - half_height;
+                let outside = (dx.max(0.0).powi(2) + dy.max(0.0).powi(2)).sqrt();
+                outside + dx.max(dy).min(0.0)
+            }
+            Sdf::Capsule {
+                radius,
+                half_height,
+            } => {
+                let y = y - y.clamp(-half_height, *half_height);
+                length(x, y, z) - radius
+            }
+            Sdf::Mandelbulb { power, iterations } => mandelbulb(x, y, z, *power, *iterations),
+            Sdf::Translated(sdf, offset) => sdf.distance(&(*point - *offset)),
+            Sdf::Scaled(sdf, factor) => {
+                let scaled = Point::new_point3D(x / factor, y / factor, z / factor);
+                sdf.distance(&scaled) * factor
+            }
+            Sdf::Union(a, b) => a.distance(point).min(b.distance(point)),
+            Sdf::Subtraction(a, b) => a.distance(point).max(-b.distance(point)),
+            Sdf::Intersection(a, b) => a.distance(point).max(b.distance(point)),
+            Sdf::SmoothUnion(a, b, k) => {
+                let (d1, d2) = (a.distance(point), b.distance(point));
+                let h = (0.5 + 0.5 * (d2 - d1) / k).clamp(0.0, 1.0);
+                mix(d2, d1, h) - k * h * (1.0 - h)
+            }
+            Sdf::SmoothSubtraction(a, b, k) => {
+                let (d1, d2) = (a.distance(point), b.distance(point));
+                let h = (0.5 - 0.5 * (d1 + d2) / k).clamp(0.0, 1.0);
+                mix(d1, -d2, h) + k * h * (1.0 - h)
+            }
+            Sdf::SmoothIntersection(a, b, k) => {
+                let (d1, d2) = (a.distance(point), b.distance(point));
+                let h = (0.5 - 0.5 * (d2 - d1) / k).clamp(0.0, 1.0);
+                mix(d2, d1, h) + k * h * (1.0 - h)
+            }
+        }
+    }
+
+    //A box the surface is guaranteed to be inside of, used to start and
+    // stop marching.
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Sdf::Sphere { radius } => cube(*radius),
+            Sdf::Cuboid { x, y, z } => Bounds::new(
+                Point::new_point3D(-x, -y, -z),
+                Point::new_point3D(*x, *y, *z),
+            ),
+            Sdf::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let outer = major_radius + minor_radius;
+                Bounds::new(
+                    Point::new_point3D(-outer, -minor_radius, -outer),
+                    Point::new_point3D(outer, *minor_radius, outer),
+                )
+            }
+            Sdf::Cylinder {
+                radius,
+                half_height,
+            } => Bounds::new(
+                Point::new_point3D(-radius, -half_height, -radius),
+                Point::new_point3D(*radius, *half_height, *radius),
+            ),
+            Sdf::Capsule {
+                radius,
+                half_height,
+            } => Bounds::new(
+                Point::new_point3D(-radius, -half_height - radius, -radius),
+                Point::new_point3D(*radius, half_height + radius, *radius),
+            ),
+            Sdf::Mandelbulb { .. } => cube(1.2),
+            Sdf::Translated(sdf, offset) => {
+                let bounds = sdf.bounds();
+                Bounds::new(bounds.min + *offset, bounds.max + *offset)
+            }
+            Sdf::Scaled(sdf, factor) => {
+                let bounds = sdf.bounds();
+                let corner = |p: Point<f64, 4>| {
+                    Point::new_point3D(p[0] * factor, p[1] * factor, p[2] * factor)
+                };
+                Bounds::new(corner(bounds.min), corner(bounds.max))
+            }
+            Sdf::Union(a, b) => a.bounds().merge(&b.bounds()),
+            Sdf::Subtraction(a, _) => a.bounds(),
+            Sdf::Intersection(a, _) => a.bounds(),
+            //The blend can bulge at most k / 4 past either shape.
+            Sdf::SmoothUnion(a, b, k) => a.bounds().merge(&b.bounds()).expand(k / 4.0),
+            Sdf::SmoothSubtraction(a, _, k) => a.bounds().expand(k / 4.0),
+            Sdf::SmoothIntersection(a, _, k) => a.bounds().expand(k / 4.0),
+        }
+    }
+}
+
+fn length(x: f64, y: f64, z: f64) -> f64 {
+    (x * x + y * y + z * z).sqrt()
+}
+
+fn mix(a: f64, b: f64, h: f64) -> f64 {
+    a + (b - a) * h
+}
+
+fn cube(half_size: f64) -> Bounds {
+    Bounds::new(
+        Point::new_point3D(-half_size, -half_size, -half_size),
+        Point::new_point3D(half_size, half_size, half_size),
+    )
+}
+
+//Distance estimate from the running derivative of z -> z^power + c in
+// spherical coordinates. It is a lower bound, which is all marching needs.
+fn mandelbulb(x: f64, y: f64, z: f64, power: f64, iterations: usize) -> f64 {
+    let (mut zx, mut zy, mut zz) = (x, y, z);
+    let mut dr = 1.0;
+    let mut r = length(zx, zy, zz);
+
+    for _ in 0..iterations {
+        if r > 2.0 || r == 0.0 {
+            break;
+        }
+
+        let theta = (zz / r).acos() * power;
+        let phi = zy.atan2(zx) * power;
+        dr = r.powf(power - 1.0) * power * dr + 1.0;
+
+        let zr = r.powf(power);
+        zx = zr * theta.sin() * phi.cos() + x;
+        zy = zr * theta.sin() * phi.sin() + y;
+        zz = zr * theta.cos() + z;
+        r = length(zx, zy, zz);
+    }
+
+    if r == 0.0 {
+        0.0
+    } else {
+        0.5 * r.ln() * r / dr
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{Point, Ray, Vector};
+
+use super::{Bounds, LocalShape, Sdf};
+
+//How close to the surface counts as a hit, and how small the steps used
+// for the gradient are.
+const HIT_DISTANCE: f64 = 1.0e-6;
+const GRADIENT_STEP: f64 = 1.0e-5;
+
+//A shape defined by a signed distance function, intersected by sphere
+// tracing: the ray can always safely advance by the distance to the
+// nearest surface.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SdfShape {
+    pub sdf: Sdf,
+    pub max_steps: usize,
+}
+
+impl SdfShape {
+    pub fn new(sdf: Sdf) -> Self {
+        Self {
+            sdf,
+            max_steps: 512,
+        }
+    }
+
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    //Marches through the bounding box and records every time the ray
+    // crosses the surface, entering or leaving, so refraction keeps
+    // working. Inside the shape it marches on the absolute distance.
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let (t_min, t_max) = self.sdf.bounds().intersect(ray)?;
+        let speed = ray.direction.magnitude();
+        let mut t = t_min;
+        let mut hits = Vec::new();
+        let mut steps = 0;
+        //The side of the surface the ray was last seen on, and where it
+        // got within HIT_DISTANCE of the surface since.
+        let mut inside = false;
+        let mut touched = None;
+
+        while t <= t_max && steps < self.max_steps {
+            let distance = self.sdf.distance(&ray.position(t));
+
+            if distance.abs() < HIT_DISTANCE {
+                touched.get_or_insert(t);
+                //Hop over the surface so the next sample is on the other side.
+                t += 4.0 * HIT_DISTANCE / speed;
+            } else {
+                //A grazing ray can spend many steps near the surface, it
+                // only crossed it if it came out on the other side.
+                if let Some(hit) = touched.take() {
+                    if (distance < 0.0) != inside {
+                        hits.push(hit);
+                    }
+                }
+                inside = distance < 0.0;
+                t += distance.abs() / speed;
+            }
+            steps += 1;
+        }
+        //Leaving the bounds on the surface means coming out of the shape.
+        if let Some(hit) = touched {
+            if inside {
+                hits.push(hit);
+            }
+        }
+
+        if hits.is_empty() {
+            None
+        } else {
+            Some(hits)
+        }
+    }
+
+    //Central differences of the distance field.
+    pub fn normal_at(&self, object_point: &Point<f64, 4>) -> Vector<f64, 4> {
+        let gradient = |axis: Vector<f64, 4>| {
+            let step = axis * GRADIENT_STEP;
+            self.sdf.distance(&(*object_point + step)) - self.sdf.distance(&(*object_point - step))
+        };
+
+        Vector::new_vec3D(
+            gradient(Vector::new_vec3D(1.0, 0.0, 0.0)),
+            gradient(Vector::new_vec3D(0.0, 1.0, 0.0)),
+            gradient(Vector::new_vec3D(0.0, 0.0, 1.0)),
+        )
+        .normalize()
+    }
+}
+
+impl LocalShape for SdfShape {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        self.normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.sdf.bounds()
+    }
+
+    //Projects the surface onto its bounding sphere, like a spherical map.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        crate::pattern::uv_mapping::spherical_map(point)
+    }
+}
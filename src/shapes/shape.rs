@@ -1,25 +1,95 @@
+use crate::color::Color;
 use crate::{Matrix, Point, Ray, Sphere, Vector};
 
-use super::Plane;
+use super::{
+    Annulus, Bounds, CustomShape, Disk, Heightfield, LocalShape, Mesh, Plane, Rectangle, SdfShape,
+    Torus,
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Shape {
     Sphere,
     Plane,
+    Disk(Disk),
+    Rectangle(Rectangle),
+    Annulus(Annulus),
+    Torus(Torus),
+    Sdf(SdfShape),
+    Heightfield(Heightfield),
+    Mesh(Mesh),
+    Custom(CustomShape),
 }
 
 impl Shape {
+    pub fn custom_shape(shape: impl LocalShape + 'static) -> Self {
+        Self::Custom(CustomShape::new(shape))
+    }
+
     pub fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
-        match *self {
+        match self {
             Self::Sphere => Sphere::intersect(ray),
             Self::Plane => Plane::intersect(ray),
+            Self::Disk(disk) => disk.intersect(ray),
+            Self::Rectangle(rectangle) => rectangle.intersect(ray),
+            Self::Annulus(annulus) => annulus.intersect(ray),
+            Self::Torus(torus) => torus.intersect(ray),
+            Self::Sdf(sdf) => sdf.intersect(ray),
+            Self::Heightfield(heightfield) => heightfield.intersect(ray),
+            Self::Mesh(mesh) => mesh.intersect(ray),
+            Self::Custom(shape) => shape.0.local_intersect(ray),
         }
     }
 
     pub fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
-        match *self {
+        match self {
             Self::Sphere => Sphere::normal_at(point),
             Self::Plane => Plane::normal_at(point),
+            Self::Disk(disk) => disk.local_normal_at(point),
+            Self::Rectangle(rectangle) => rectangle.local_normal_at(point),
+            Self::Annulus(annulus) => annulus.local_normal_at(point),
+            Self::Torus(torus) => torus.normal_at(point),
+            Self::Sdf(sdf) => sdf.normal_at(point),
+            Self::Heightfield(heightfield) => heightfield.normal_at(point),
+            Self::Mesh(mesh) => mesh.normal_at(point),
+            Self::Custom(shape) => shape.0.local_normal_at(point),
+        }
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        match self {
+            Self::Sphere => Sphere::new().bounds(),
+            Self::Plane => Plane::new().bounds(),
+            Self::Disk(disk) => disk.bounds(),
+            Self::Rectangle(rectangle) => rectangle.bounds(),
+            Self::Annulus(annulus) => annulus.bounds(),
+            Self::Torus(torus) => torus.bounds(),
+            Self::Sdf(sdf) => sdf.bounds(),
+            Self::Heightfield(heightfield) => heightfield.bounds(),
+            Self::Mesh(mesh) => mesh.bounds(),
+            Self::Custom(shape) => shape.0.bounds(),
+        }
+    }
+
+    pub fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        match self {
+            Self::Sphere => Sphere::new().local_uv(point),
+            Self::Plane => Plane::new().local_uv(point),
+            Self::Disk(disk) => disk.local_uv(point),
+            Self::Rectangle(rectangle) => rectangle.local_uv(point),
+            Self::Annulus(annulus) => annulus.local_uv(point),
+            Self::Torus(torus) => torus.local_uv(point),
+            Self::Sdf(sdf) => sdf.local_uv(point),
+            Self::Heightfield(heightfield) => heightfield.local_uv(point),
+            Self::Mesh(mesh) => mesh.local_uv(point),
+            Self::Custom(shape) => shape.0.local_uv(point),
+        }
+    }
+
+    //Color stored on the vertices of the shape, if it has any.
+    pub fn vertex_color_at(&self, point: &Point<f64, 4>) -> Option<Color> {
+        match self {
+            Self::Mesh(mesh) => mesh.color_at(point),
+            _ => None,
         }
     }
 }
@@ -1,5 +1,8 @@
+use crate::pattern::uv_mapping::spherical_map;
 use crate::{Matrix, Point, Ray, Vector};
 
+use super::{Bounds, LocalShape};
+
 // We assume a sphere is always at Position{0, 0 , 0}, thus the absence of coordinates.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Sphere {}
@@ -33,3 +36,24 @@ impl Sphere {
         object_normal
     }
 }
+
+impl LocalShape for Sphere {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        Sphere::intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        Sphere::normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new_point3D(-1.0, -1.0, -1.0),
+            Point::new_point3D(1.0, 1.0, 1.0),
+        )
+    }
+
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        spherical_map(point)
+    }
+}
@@ -0,0 +1,102 @@
+use std::f64::consts::PI;
+
+use crate::utils::polynomial::solve_quartic;
+use crate::{Point, Ray, Vector};
+
+use super::{Bounds, LocalShape};
+
+//A ring centered at the origin, lying on the xz plane. `major_radius` goes
+// from the center to the middle of the tube, `minor_radius` is the
+// radius of the tube itself.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Torus {
+    pub major_radius: f64,
+    pub minor_radius: f64,
+}
+
+impl Torus {
+    pub fn new(major_radius: f64, minor_radius: f64) -> Self {
+        Torus {
+            major_radius,
+            minor_radius,
+        }
+    }
+
+    //Substitutes the ray into (x² + y² + z² + R² - r²)² = 4R²(x² + z²).
+    // The ray is first moved up to the sphere enclosing the torus, so a far
+    // away origin does not cost precision in the quartic.
+    pub fn intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        let big_r2 = self.major_radius.powi(2);
+        let small_r2 = self.minor_radius.powi(2);
+        let enclosing_r2 = (self.major_radius + self.minor_radius).powi(2);
+
+        let a = ray.direction * ray.direction;
+        let start = ray.origin - Point::new_point3D(0.0, 0.0, 0.0);
+        let b = ray.direction * start;
+        let c = start * start - enclosing_r2;
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let shift = (-b - discriminant.sqrt()) / a;
+
+        let origin = ray.position(shift);
+        let (ox, oy, oz) = (origin[0], origin[1], origin[2]);
+        let (dx, dy, dz) = (ray.direction[0], ray.direction[1], ray.direction[2]);
+
+        let m = a;
+        let n = ox * dx + oy * dy + oz * dz;
+        let k = ox * ox + oy * oy + oz * oz + big_r2 - small_r2;
+
+        let roots = solve_quartic(
+            m * m,
+            4.0 * m * n,
+            4.0 * n * n + 2.0 * m * k - 4.0 * big_r2 * (dx * dx + dz * dz),
+            4.0 * n * k - 8.0 * big_r2 * (ox * dx + oz * dz),
+            k * k - 4.0 * big_r2 * (ox * ox + oz * oz),
+        );
+
+        if roots.is_empty() {
+            None
+        } else {
+            Some(roots.iter().map(|t| t + shift).collect())
+        }
+    }
+
+    //Gradient of the implicit equation, up to a constant factor.
+    pub fn normal_at(&self, object_point: &Point<f64, 4>) -> Vector<f64, 4> {
+        let (x, y, z) = (object_point[0], object_point[1], object_point[2]);
+        let big_r2 = self.major_radius.powi(2);
+        let sum = x * x + y * y + z * z + big_r2 - self.minor_radius.powi(2);
+
+        Vector::new_vec3D(x * (sum - 2.0 * big_r2), y * sum, z * (sum - 2.0 * big_r2))
+    }
+}
+
+impl LocalShape for Torus {
+    fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+        self.intersect(ray)
+    }
+
+    fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+        self.normal_at(point)
+    }
+
+    fn bounds(&self) -> Bounds {
+        let outer = self.major_radius + self.minor_radius;
+        Bounds::new(
+            Point::new_point3D(-outer, -self.minor_radius, -outer),
+            Point::new_point3D(outer, self.minor_radius, outer),
+        )
+    }
+
+    //u goes around the ring, v around the tube starting from its outer edge.
+    fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+        let (x, y, z) = (point[0], point[1], point[2]);
+        let u = 0.5 + f64::atan2(x, z) / (2.0 * PI);
+        let from_ring = (x * x + z * z).sqrt() - self.major_radius;
+        let v = f64::atan2(y, from_ring).rem_euclid(2.0 * PI) / (2.0 * PI);
+
+        (u, v)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{Point, Ray};
+
+//Möller-Trumbore. Returns t and the barycentric weights of p1 and p2
+// at the hit, the weight of p0 being 1 - u - v.
+pub fn intersect_triangle(
+    ray: &Ray,
+    p0: &Point<f64, 4>,
+    p1: &Point<f64, 4>,
+    p2: &Point<f64, 4>,
+) -> Option<(f64, f64, f64)> {
+    let e1 = *p1 - *p0;
+    let e2 = *p2 - *p0;
+    let dir_cross_e2 = ray.direction | e2;
+    let det = e1 * dir_cross_e2;
+    if det.abs() < 1.0e-12 {
+        return None;
+    }
+
+    let f = 1.0 / det;
+    let p0_to_origin = ray.origin - *p0;
+    let u = f * (p0_to_origin * dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p0_to_origin | e1;
+    let v = f * (ray.direction * origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    Some((f * (e2 * origin_cross_e1), u, v))
+}
+
+//Barycentric weights of the point projected onto the triangle's plane,
+// and how far the point is from that plane.
+pub fn barycentric(
+    point: &Point<f64, 4>,
+    p0: &Point<f64, 4>,
+    p1: &Point<f64, 4>,
+    p2: &Point<f64, 4>,
+) -> Option<([f64; 3], f64)> {
+    let e1 = *p1 - *p0;
+    let e2 = *p2 - *p0;
+    let to_point = *point - *p0;
+
+    let d11 = e1 * e1;
+    let d12 = e1 * e2;
+    let d22 = e2 * e2;
+    let denominator = d11 * d22 - d12 * d12;
+    if denominator.abs() < 1.0e-24 {
+        return None;
+    }
+
+    let dp1 = to_point * e1;
+    let dp2 = to_point * e2;
+    let v = (d22 * dp1 - d12 * dp2) / denominator;
+    let w = (d11 * dp2 - d12 * dp1) / denominator;
+    let normal = (e1 | e2).normalize();
+
+    Some(([1.0 - v - w, v, w], (to_point * normal).abs()))
+}
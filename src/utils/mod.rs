@@ -8,3 +8,5 @@ pub use zero::Zero;
 
 pub mod one;
 pub use one::One;
+
+pub mod polynomial;
@@ -0,0 +1,158 @@
+//Real roots of polynomials, for shapes whose surface equation goes beyond
+// the quadratic of a sphere. Coefficients go from the highest power down.
+
+//How close to zero a local minimum or maximum has to be, relative to the
+// size of the terms being summed, to count as touching the axis.
+const TANGENT_TOLERANCE: f64 = 1.0e-10;
+const MAX_ITERATIONS: usize = 100;
+
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    real_roots(&[a, b, c])
+}
+
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    real_roots(&[a, b, c, d])
+}
+
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    real_roots(&[a, b, c, d, e])
+}
+
+//Sorted real roots. Between two consecutive roots of the derivative the
+// polynomial is monotonic, so each of those intervals holds at most one
+// root, which is then refined with a bracketed Newton search. This avoids
+// the cancellation that makes the closed form cubic and quartic formulas
+// fall apart. A double root, like a ray grazing a surface, is reported twice.
+pub fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    let coefficients = match coefficients.iter().position(|c| *c != 0.0) {
+        Some(first) => &coefficients[first..],
+        None => return Vec::new(),
+    };
+    let degree = coefficients.len() - 1;
+
+    match degree {
+        0 => Vec::new(),
+        1 => vec![-coefficients[1] / coefficients[0]],
+        2 => quadratic_roots(coefficients[0], coefficients[1], coefficients[2]),
+        _ => isolated_roots(coefficients),
+    }
+}
+
+pub fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().fold(0.0, |sum, c| sum * x + c)
+}
+
+pub fn derivative(coefficients: &[f64]) -> Vec<f64> {
+    let degree = coefficients.len().saturating_sub(1);
+
+    coefficients[..degree]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c * (degree - i) as f64)
+        .collect()
+}
+
+//Written so that b and the square root never cancel each other out.
+fn quadratic_roots(a: f64, b: f64, c: f64) -> Vec<f64> {
+    let discriminant = b * b - 4.0 * a * c;
+
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let mut roots = if q == 0.0 {
+        vec![0.0, 0.0]
+    } else {
+        vec![q / a, c / q]
+    };
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots
+}
+
+fn isolated_roots(coefficients: &[f64]) -> Vec<f64> {
+    let derivative = derivative(coefficients);
+    //Cauchy's bound, every root lies inside (-bound, bound).
+    let bound = 1.0
+        + coefficients[1..]
+            .iter()
+            .map(|c| (c / coefficients[0]).abs())
+            .fold(0.0, f64::max);
+
+    let mut edges = vec![-bound];
+    for critical in real_roots(&derivative) {
+        if critical > *edges.last().unwrap() && critical < bound {
+            edges.push(critical);
+        }
+    }
+    edges.push(bound);
+
+    let mut values: Vec<f64> = edges.iter().map(|x| evaluate(coefficients, *x)).collect();
+    let mut roots = Vec::new();
+
+    for i in 1..edges.len() - 1 {
+        let x = edges[i];
+        if values[i].abs() <= TANGENT_TOLERANCE * magnitude(coefficients, x) {
+            roots.push(x);
+            //Touching the axis without crossing it counts twice.
+            if values[i - 1].signum() == values[i + 1].signum() {
+                roots.push(x);
+            }
+            values[i] = 0.0;
+        }
+    }
+
+    for i in 0..edges.len() - 1 {
+        if values[i] * values[i + 1] < 0.0 {
+            roots.push(refine(coefficients, &derivative, edges[i], edges[i + 1]));
+        }
+    }
+
+    roots.sort_by(|a, b| a.total_cmp(b));
+    roots
+}
+
+//Sum of the absolute values of the terms at x, the scale rounding errors
+// in evaluate are relative to.
+fn magnitude(coefficients: &[f64], x: f64) -> f64 {
+    coefficients
+        .iter()
+        .fold(0.0, |sum, c| sum * x.abs() + c.abs())
+}
+
+//Newton's method kept inside [low, high], falling back to bisection
+// whenever a step would leave the bracket.
+fn refine(coefficients: &[f64], derivative: &[f64], low: f64, high: f64) -> f64 {
+    let mut low = low;
+    let mut high = high;
+    let low_is_negative = evaluate(coefficients, low) < 0.0;
+    let mut x = 0.5 * (low + high);
+
+    for _ in 0..MAX_ITERATIONS {
+        let value = evaluate(coefficients, x);
+        if value == 0.0 {
+            break;
+        }
+
+        if (value < 0.0) == low_is_negative {
+            low = x;
+        } else {
+            high = x;
+        }
+
+        let slope = evaluate(derivative, x);
+        let newton = x - value / slope;
+        let next = if slope != 0.0 && newton > low && newton < high {
+            newton
+        } else {
+            0.5 * (low + high)
+        };
+
+        if (next - x).abs() <= f64::EPSILON * x.abs().max(1.0) {
+            return next;
+        }
+        x = next;
+    }
+
+    x
+}
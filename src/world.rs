@@ -1,8 +1,9 @@
+use crate::color::{Channel, Color};
 use crate::intersection::Computations;
 use crate::transformation::scaling;
-use crate::{color::Color, Light, Point};
 use crate::{ApproximateEq, Pattern, PatternType};
 use crate::{Intersection, Intersections, Object, Ray};
+use crate::{Light, Point};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct World {
@@ -93,17 +94,28 @@ impl World {
     }
 
     pub fn shade_hit(&self, comps: &Computations, remaing: u8) -> Color {
-        let shadowed = self.is_shadowed(&comps.over_point);
-        let surface = comps.object.material.lightning(
+        self.shade_hit_channel(comps, remaing, None)
+    }
+
+    //`channel` is set once a ray has been split by dispersion, from then on
+    // it only carries the light of that channel's wavelength.
+    fn shade_hit_channel(
+        &self,
+        comps: &Computations,
+        remaing: u8,
+        channel: Option<Channel>,
+    ) -> Color {
+        let transmittance = self.shadow_transmittance(&comps.over_point);
+        let surface = comps.object.material.lightning_with_transmittance(
             comps.object,
             &self.light,
             &comps.over_point,
             &comps.eyev,
             &comps.normalv,
-            shadowed,
+            transmittance,
         );
-        let reflected = self.reflected_color(&comps, remaing);
-        let refracted = self.refracted_color(&comps, remaing);
+        let reflected = self.reflected_color_channel(&comps, remaing, channel);
+        let refracted = self.refracted_color_channel(&comps, remaing, channel);
 
         if comps.object.material.reflective > 0.0 && comps.object.material.transparency > 0.0 {
             let reflectance = comps.schlick();
@@ -114,70 +126,188 @@ impl World {
     }
 
     pub fn is_shadowed(&self, point: &Point<f64, 4>) -> bool {
+        self.shadow_transmittance(point) == Color::black()
+    }
+
+    //How much of the light reaches `point`. Transparent objects in the way
+    // let part of it through, tinted by their color and absorbed along the
+    // way through them, while opaque ones block it completely. Objects
+    // that don't cast shadows are ignored.
+    pub fn shadow_transmittance(&self, point: &Point<f64, 4>) -> Color {
         let v = self.light.position - *point;
         let distance = v.magnitude();
         let direction = v.normalize();
-        let mut shadowed = false;
+        let mut transmittance = Color::white();
+        //each object tints the light once, no matter how many times it is crossed
+        let mut tinted: Vec<&Object> = Vec::new();
+        //the objects the ray is inside of, and where it went in
+        let mut inside: Vec<(&Object, f64)> = Vec::new();
 
         let r = Ray::new(*point, direction);
-        let mut inters = self.intersect_world(&r);
+        let inters = self.intersect_world(&r);
 
-        if let Some(hit) = inters.hit() {
-            if hit.get_t() < distance {
-                shadowed = true;
+        for inter in inters.into_iter() {
+            if inter.get_t() >= distance {
+                break;
             }
+
+            let object = inter.get_object();
+            if !object.casts_shadow {
+                continue;
+            }
+
+            //hits behind the point only tell which objects it starts in
+            let t = inter.get_t().max(0.0);
+            if inter.get_t() >= 0.0 {
+                let material = &object.material;
+                if material.transparency.approx_eq(&0.0) {
+                    return Color::black();
+                }
+
+                if !tinted.iter().any(|&other| std::ptr::eq(other, object)) {
+                    tinted.push(object);
+                    transmittance = transmittance * material.color * material.transparency;
+                }
+            }
+
+            match inside
+                .iter()
+                .position(|&(other, _)| std::ptr::eq(other, object))
+            {
+                Some(pos) => {
+                    let (_, entry) = inside.remove(pos);
+                    transmittance = transmittance * object.material.transmittance(t - entry);
+                }
+                None => inside.push((object, t)),
+            }
+        }
+        //the light is inside whatever is still open
+        for (object, entry) in inside {
+            transmittance = transmittance * object.material.transmittance(distance - entry);
         }
 
-        shadowed
+        transmittance
     }
 
     pub fn reflected_color(&self, comps: &Computations, remaing: u8) -> Color {
+        self.reflected_color_channel(comps, remaing, None)
+    }
+
+    fn reflected_color_channel(
+        &self,
+        comps: &Computations,
+        remaing: u8,
+        channel: Option<Channel>,
+    ) -> Color {
         if comps.object.material.reflective.approx_eq_low(&0.0) || remaing == 0 {
             Color::black()
         } else {
             let relfect_ray = Ray::new(comps.over_point.clone(), comps.reflectv.clone());
-            let color = self.color_at(&relfect_ray, remaing - 1);
+            let (color, distance) = self.trace(&relfect_ray, remaing - 1, channel);
+            //the reflected ray stays in the medium it came from
+            let color = self.absorbed(color, comps.n1_object, distance);
 
             color * comps.object.material.reflective
         }
     }
 
     pub fn refracted_color(&self, comps: &Computations, remaing: u8) -> Color {
+        self.refracted_color_channel(comps, remaing, None)
+    }
+
+    fn refracted_color_channel(
+        &self,
+        comps: &Computations,
+        remaing: u8,
+        channel: Option<Channel>,
+    ) -> Color {
         if comps.object.material.transparency.approx_eq(&0.0) || remaing == 0 {
             Color::black()
+        } else if channel.is_none() && comps.is_dispersive() {
+            //Every channel bends by a different angle, so each one is
+            // traced on its own and only keeps its own component.
+            Channel::ALL
+                .iter()
+                .map(|&channel| {
+                    let n1 = comps.n1_at(channel.wavelength());
+                    let n2 = comps.n2_at(channel.wavelength());
+                    let color = self.refract(comps, n1, n2, remaing, Some(channel));
+                    Color::from_channel(channel, color.channel(channel))
+                })
+                .fold(Color::black(), |acc, color| acc + color)
         } else {
-            //this is inverted from the definition of Snell's Law.
-            let n_ratio = comps.n1 / comps.n2;
-            // cos(theta_i) is the same as the dot product of the two vectors
-            let cos_i = comps.eyev * comps.normalv;
-            // sin(theta_t)^2 via trigonometric identity
-            let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
-
-            if sin2_t > 1.0 {
-                return Color::black();
-            }
-            //cos(theta_t) via trigonometric identity
-            let cos_t = f64::sqrt(1.0 - sin2_t);
-            //Compute the direction of the refracted ray
-            let direction = comps.normalv * (n_ratio * cos_i - cos_t) - comps.eyev * n_ratio;
-            //Create the refracted ray
-            let refract_ray = Ray::new(comps.under_point, direction);
+            let (n1, n2) = match channel {
+                Some(channel) => (
+                    comps.n1_at(channel.wavelength()),
+                    comps.n2_at(channel.wavelength()),
+                ),
+                None => (comps.n1, comps.n2),
+            };
+            self.refract(comps, n1, n2, remaing, channel)
+        }
+    }
 
-            let color =
-                self.color_at(&refract_ray, remaing - 1) * comps.object.material.transparency;
+    fn refract(
+        &self,
+        comps: &Computations,
+        n1: f64,
+        n2: f64,
+        remaing: u8,
+        channel: Option<Channel>,
+    ) -> Color {
+        //this is inverted from the definition of Snell's Law.
+        let n_ratio = n1 / n2;
+        // cos(theta_i) is the same as the dot product of the two vectors
+        let cos_i = comps.eyev * comps.normalv;
+        // sin(theta_t)^2 via trigonometric identity
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
 
-            color
+        if sin2_t > 1.0 {
+            return Color::black();
         }
+        //cos(theta_t) via trigonometric identity
+        let cos_t = f64::sqrt(1.0 - sin2_t);
+        //Compute the direction of the refracted ray
+        let direction = comps.normalv * (n_ratio * cos_i - cos_t) - comps.eyev * n_ratio;
+        //Create the refracted ray
+        let refract_ray = Ray::new(comps.under_point, direction);
+
+        let (color, distance) = self.trace(&refract_ray, remaing - 1, channel);
+        //the refracted ray crosses into the medium on the other side
+        let color = self.absorbed(color, comps.n2_object, distance);
+
+        color * comps.object.material.transparency
     }
 
     pub fn color_at(&self, ray: &Ray, remaing: u8) -> Color {
-        let mut color = Color::black();
+        let (color, _) = self.trace(ray, remaing, None);
+        color
+    }
+
+    //Same as color_at, but also returns how far the ray travelled
+    // before hitting something, or None when it escaped the world.
+    fn trace(&self, ray: &Ray, remaing: u8, channel: Option<Channel>) -> (Color, Option<f64>) {
         let inters = self.intersect_world(ray);
 
         if let Some(hit) = inters.hit_index() {
             let comps = Computations::prepare_computation(&inters, hit, ray);
-            color = self.shade_hit(&comps, remaing);
+            (
+                self.shade_hit_channel(&comps, remaing, channel),
+                Some(comps.t),
+            )
+        } else {
+            (Color::black(), None)
+        }
+    }
+
+    //Attenuates a color that travelled `distance` inside `medium`.
+    fn absorbed(&self, color: Color, medium: Option<&Object>, distance: Option<f64>) -> Color {
+        match medium {
+            Some(object) => {
+                let distance = distance.unwrap_or(f64::INFINITY);
+                color * object.material.transmittance(distance)
+            }
+            None => color,
         }
-        color
     }
 }
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod aovs_test {
+    use std::f64::consts::PI;
+    use std::io::Cursor;
+
+    use rtc::color::Color;
+    use rtc::transformation::view_transform;
+    use rtc::{Camera, Point, Vector, World};
+
+    fn camera() -> Camera {
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Point::new_point3D(0.0, 0.0, -5.0);
+        let to = Point::new_point3D(0.0, 0.0, 0.0);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        c.set_transformation(&view_transform(&from, &to, &up));
+        c
+    }
+
+    #[test]
+    fn aovs_of_a_hit() {
+        let w = World::default_test();
+        let (image, aovs) = camera().render_with_aovs(&w);
+
+        assert_eq!(w.color_at(&camera().ray_for_pixel(5, 5), 4), image[5][5]);
+        assert_eq!(Color::new(4.0, 4.0, 4.0), aovs.depth[5][5]);
+        assert_eq!(Color::new(0.0, 0.0, -1.0), aovs.normal[5][5]);
+        assert_eq!(Color::new(0.8, 1.0, 0.6), aovs.albedo[5][5]);
+        assert_eq!(Color::new(1.0, 1.0, 1.0), aovs.object_id[5][5]);
+        assert_eq!(Color::new(0.0, 0.0, -1.0), aovs.position[5][5]);
+    }
+
+    #[test]
+    fn aovs_where_nothing_is_hit() {
+        let w = World::default_test();
+        let (image, aovs) = camera().render_with_aovs(&w);
+
+        assert_eq!(Color::black(), image[0][0]);
+        assert_eq!(0.0, image.alpha_at(0, 0));
+        assert_eq!(1.0, image.alpha_at(5, 5));
+        assert_eq!(f64::INFINITY, aovs.depth[0][0].red);
+        assert_eq!(Color::black(), aovs.normal[0][0]);
+        assert_eq!(Color::black(), aovs.object_id[0][0]);
+    }
+
+    #[test]
+    fn object_ids_follow_the_world_order() {
+        let mut w = World::default_test();
+        // only the inner sphere is left
+        w.objects.remove(0);
+        let (_, aovs) = camera().render_with_aovs(&w);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), aovs.object_id[5][5]);
+        assert_eq!(Color::new(4.5, 4.5, 4.5), aovs.depth[5][5]);
+        assert_eq!(Color::white(), aovs.albedo[5][5]);
+
+        let mut w = World::default_test();
+        w.objects[0].set_transformation(rtc::transformation::translation(0.0, 0.0, 10.0));
+        let (_, aovs) = camera().render_with_aovs(&w);
+
+        assert_eq!(Color::new(2.0, 2.0, 2.0), aovs.object_id[5][5]);
+    }
+
+    #[test]
+    fn the_image_matches_a_plain_render() {
+        let w = World::default_test();
+        let c = camera();
+        let plain = c.render(&w);
+        let (image, _) = c.render_with_aovs(&w);
+
+        for y in 0..c.vsize {
+            for x in 0..c.hsize {
+                assert_eq!(plain[y][x], image[y][x]);
+            }
+        }
+    }
+
+    #[test]
+    fn aovs_can_be_exported() {
+        let w = World::default_test();
+        let (_, aovs) = camera().render_with_aovs(&w);
+        let mut exr = Cursor::new(Vec::new());
+        aovs.depth.write_exr(&mut exr).unwrap();
+        let mut png = Vec::new();
+        aovs.albedo
+            .write_png(&mut png, rtc::PngOptions::new())
+            .unwrap();
+
+        let depth = ::image::load_from_memory(exr.get_ref())
+            .unwrap()
+            .to_rgb32f();
+        assert_eq!(4.0, depth.get_pixel(5, 5)[0]);
+        assert_eq!(f32::INFINITY, depth.get_pixel(0, 0)[0]);
+        let albedo = ::image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(255, albedo.get_pixel(5, 5)[1]);
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod bezier_test {
+    use rtc::loaders::patch::parse_patches;
+    use rtc::loaders::{load_patches, PatchError};
+    use rtc::shapes::bezier::tessellate;
+    use rtc::{ApproximateEq, BezierPatch, Point, Ray, Vector};
+
+    fn point(x: f64, y: f64, z: f64) -> Point<f64, 4> {
+        Point::new_point3D(x, y, z)
+    }
+
+    //Control points on a regular grid over the square (0, 0) to (3, 3),
+    // each lifted by `height(col, row)`.
+    fn patch(height: impl Fn(usize, usize) -> f64) -> BezierPatch {
+        let mut control_points = [point(0.0, 0.0, 0.0); 16];
+        for row in 0..4 {
+            for col in 0..4 {
+                control_points[row * 4 + col] = point(col as f64, height(col, row), row as f64);
+            }
+        }
+        BezierPatch::new(control_points)
+    }
+
+    #[test]
+    fn corners_of_a_patch_are_its_corner_control_points() {
+        let patch = patch(|col, row| (col * row) as f64);
+
+        assert_eq!(point(0.0, 0.0, 0.0), patch.point_at(0.0, 0.0));
+        assert_eq!(point(3.0, 0.0, 0.0), patch.point_at(1.0, 0.0));
+        assert_eq!(point(3.0, 9.0, 3.0), patch.point_at(1.0, 1.0));
+    }
+
+    #[test]
+    fn flat_patch_is_a_plane() {
+        let patch = patch(|_, _| 0.0);
+
+        assert_eq!(point(1.5, 0.0, 0.75), patch.point_at(0.5, 0.25));
+        let normal = patch.normal_at(0.3, 0.8);
+        assert_eq!(true, normal[1].abs().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn tangents_of_a_patch() {
+        let patch = patch(|col, _| col as f64);
+        let (tangent_u, tangent_v) = patch.tangents_at(0.5, 0.5);
+
+        assert_eq!(Vector::new_vec3D(3.0, 3.0, 0.0), tangent_u);
+        assert_eq!(Vector::new_vec3D(0.0, 0.0, 3.0), tangent_v);
+    }
+
+    #[test]
+    fn collapsed_edge_still_has_a_normal() {
+        // every point of the first row is the apex of a cone-like cap
+        let mut patch = patch(|_, row| -(row as f64));
+        for col in 0..4 {
+            patch.control_points[col] = point(1.5, 1.0, 0.0);
+        }
+
+        let normal = patch.normal_at(0.5, 0.0);
+
+        assert_eq!(true, normal.magnitude().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn tessellated_patch_follows_the_surface() {
+        let patch = patch(|col, row| {
+            if (1..3).contains(&col) && (1..3).contains(&row) {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        let mesh = patch.tessellate(16);
+
+        assert_eq!(17 * 17, mesh.positions().len());
+        assert_eq!(16 * 16 * 2, mesh.triangles().len());
+
+        let surface = patch.point_at(0.5, 0.5);
+        let ray = Ray::new(
+            point(surface[0], 5.0, surface[2]),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        );
+        let hit = mesh.intersect(&ray).unwrap()[0];
+        assert_eq!(true, (5.0 - hit - surface[1]).abs() < 1.0e-2);
+
+        let normal = mesh.normal_at(&ray.position(hit));
+        assert_eq!(true, normal[1].abs().approx_eq_low(&1.0));
+    }
+
+    #[test]
+    fn tessellating_several_patches_into_one_mesh() {
+        let a = patch(|_, _| 0.0);
+        let b = patch(|_, _| 1.0);
+        let mesh = tessellate(&[a, b], 4);
+
+        assert_eq!(2 * 25, mesh.positions().len());
+        let ray = Ray::new(point(1.0, 5.0, 1.0), Vector::new_vec3D(0.0, -1.0, 0.0));
+        assert_eq!(Some(vec![4.0, 5.0]), mesh.intersect(&ray));
+    }
+
+    const INDEXED: &str = "2
+1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16
+4, 3, 2, 1, 8, 7, 6, 5, 12, 11, 10, 9, 16, 15, 14, 13
+
+16
+0.0,0.0,0.0
+1.0,0.0,0.0
+2.0,0.0,0.0
+3.0,0.0,0.0
+0.0,0.0,1.0
+1.0,1.0,1.0
+2.0,1.0,1.0
+3.0,0.0,1.0
+0.0,0.0,2.0
+1.0,1.0,2.0
+2.0,1.0,2.0
+3.0,0.0,2.0
+0.0,0.0,3.0
+1.0,0.0,3.0
+2.0,0.0,3.0
+3.0, 0.0, 3.0
+";
+
+    #[test]
+    fn parsing_an_indexed_patch_file() {
+        let patches = parse_patches(INDEXED).unwrap();
+
+        assert_eq!(2, patches.len());
+        assert_eq!(point(1.0, 1.0, 1.0), patches[0].control_points[5]);
+        assert_eq!(point(3.0, 0.0, 0.0), patches[1].control_points[0]);
+        assert_eq!(patches[0].point_at(0.5, 0.5), patches[1].point_at(0.5, 0.5));
+    }
+
+    #[test]
+    fn parsing_a_bpt_patch_file() {
+        let mut source = String::from("1\n3 3\n");
+        for row in 0..4 {
+            for col in 0..4 {
+                source.push_str(&format!("{} {} {}\n", col, row * col, row));
+            }
+        }
+
+        let patches = parse_patches(&source).unwrap();
+
+        assert_eq!(1, patches.len());
+        assert_eq!(point(3.0, 9.0, 3.0), patches[0].control_points[15]);
+    }
+
+    #[test]
+    fn loading_a_patch_file() {
+        let path = std::env::temp_dir().join("rtc_bezier_test.txt");
+        std::fs::write(&path, INDEXED).unwrap();
+
+        assert_eq!(2, load_patches(&path).unwrap().len());
+        assert_eq!(
+            true,
+            matches!(
+                load_patches(std::env::temp_dir().join("rtc_missing.bpt")),
+                Err(PatchError::Io(_))
+            )
+        );
+    }
+
+    #[test]
+    fn malformed_patch_files() {
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches("1\n1,2,3\n"),
+                Err(PatchError::Parse { line: 2, .. })
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches(&INDEXED.replace("3.0, 0.0, 3.0", "3.0, zero, 3.0")),
+                Err(PatchError::Parse { line: 21, .. })
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches(&INDEXED.replace("16\n0.0", "15\n0.0")),
+                Err(PatchError::Parse { line: 2, .. })
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches("1\n3 3\n0 0 0\n"),
+                Err(PatchError::UnexpectedEnd)
+            )
+        );
+    }
+
+    #[test]
+    fn huge_counts_in_a_short_patch_file() {
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches("99999999999999999\n3 3\n0 0 0\n"),
+                Err(PatchError::UnexpectedEnd)
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches("99999999999999999\n1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16\n"),
+                Err(PatchError::UnexpectedEnd)
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_patches(&INDEXED.replace("16\n0.0", "99999999999999999\n0.0")),
+                Err(PatchError::UnexpectedEnd)
+            )
+        );
+    }
+}
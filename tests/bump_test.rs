@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod bump_test {
+    use rtc::{
+        bump::NormalMap, color::Color, pattern::image_texture::Filter, transformation::translation,
+        Bump, ImageTexture, Matrix, Mesh, Object, Point, UvMapping, Vector,
+    };
+    use std::f64::consts::PI;
+
+    #[test]
+    fn without_bump_the_normal_is_unchanged() {
+        let plane = Object::new_plane();
+        let n = plane.normal_at(&Point::new_point3D(1.0, 0.0, 2.0));
+
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), n);
+    }
+
+    #[test]
+    fn ripples_tilt_the_normal_away_from_the_slope() {
+        let plane = Object::new_plane().with_bump(Bump::ripple_bump(0.5, PI));
+
+        // on a crest the surface is flat
+        let crest = plane.normal_at(&Point::new_point3D(0.5, 0.0, 0.0));
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), crest);
+
+        // one unit away the surface falls with a slope of 0.5 * pi
+        let slope = 0.5 * PI;
+        let n = plane.normal_at(&Point::new_point3D(1.0, 0.0, 0.0));
+        let expected = Vector::new_vec3D(slope, 1.0, 0.0).normalize();
+        assert_eq!(expected, n);
+    }
+
+    #[test]
+    fn bump_follows_the_object_transformation() {
+        let plane = Object::new_plane()
+            .with_transformation(translation(3.0, 0.0, 0.0))
+            .with_bump(Bump::ripple_bump(0.5, PI));
+
+        let crest = plane.normal_at(&Point::new_point3D(3.5, 0.0, 0.0));
+
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), crest);
+    }
+
+    #[test]
+    fn normal_from_a_height_function() {
+        let plane = Object::new_plane().with_bump(Bump::height_bump(|p| p[0] * 2.0, 0.5));
+        let n = plane.normal_at(&Point::new_point3D(0.3, 0.0, 0.7));
+
+        assert_eq!(Vector::new_vec3D(-1.0, 1.0, 0.0).normalize(), n);
+    }
+
+    #[test]
+    fn flat_normal_map_keeps_the_normal() {
+        let texture = ImageTexture::from_colors(1, 1, vec![Color::new(0.5, 0.5, 1.0)]);
+        let map = NormalMap::new(texture, UvMapping::Planar, 1.0);
+        let plane = Object::new_plane().with_bump(Bump::NormalMap(map));
+
+        let n = plane.normal_at(&Point::new_point3D(0.2, 0.0, 0.4));
+
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), n);
+    }
+
+    #[test]
+    fn normal_map_is_read_in_tangent_space() {
+        // left half points along u (+x), right half along v (+z)
+        let texture = ImageTexture::from_colors(
+            2,
+            1,
+            vec![Color::new(1.0, 0.5, 0.5), Color::new(0.5, 1.0, 0.5)],
+        )
+        .with_filter(Filter::Nearest);
+        let map = NormalMap::new(texture, UvMapping::Planar, 1.0);
+        let plane = Object::new_plane().with_bump(Bump::NormalMap(map));
+
+        let along_u = plane.normal_at(&Point::new_point3D(0.25, 0.0, 0.5));
+        let along_v = plane.normal_at(&Point::new_point3D(0.75, 0.0, 0.5));
+
+        assert_eq!(Vector::new_vec3D(1.0, 0.0, 0.0), along_u);
+        assert_eq!(Vector::new_vec3D(0.0, 0.0, 1.0), along_v);
+    }
+
+    #[test]
+    fn normal_map_through_the_surface_uv() {
+        //u goes along z on this square, so a normal along u leans to +z
+        let mesh = Mesh::new(
+            vec![
+                Point::new_point3D(0.0, 0.0, 0.0),
+                Point::new_point3D(1.0, 0.0, 0.0),
+                Point::new_point3D(1.0, 0.0, 1.0),
+                Point::new_point3D(0.0, 0.0, 1.0),
+            ],
+            vec![[0, 2, 1], [0, 3, 2]],
+        )
+        .with_uvs(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+        let texture = ImageTexture::from_colors(1, 1, vec![Color::new(1.0, 0.5, 0.5)]);
+        let map = NormalMap::new(texture, UvMapping::Surface, 1.0);
+        let square = Object::new_mesh(mesh).with_bump(Bump::NormalMap(map.clone()));
+        let p = Point::new_point3D(0.3, 0.0, 0.6);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+
+        assert_eq!(Vector::new_vec3D(0.0, 0.0, 1.0), square.normal_at(&p));
+        //nowhere to read uv from, the normal stays
+        assert_eq!(up, map.normal_at(&p, up, &Matrix::iden()));
+    }
+
+    #[test]
+    fn normal_map_on_a_sphere_stays_outward_when_flat() {
+        let texture = ImageTexture::from_colors(1, 1, vec![Color::new(0.5, 0.5, 1.0)]);
+        let map = NormalMap::new(texture, UvMapping::Spherical, 1.0);
+        let sphere = Object::new_sphere().with_bump(Bump::NormalMap(map));
+        let half_sqrt2 = 2.0f64.sqrt() / 2.0;
+
+        let n = sphere.normal_at(&Point::new_point3D(half_sqrt2, 0.0, half_sqrt2));
+
+        assert_eq!(Vector::new_vec3D(half_sqrt2, 0.0, half_sqrt2), n);
+    }
+
+    #[test]
+    fn noise_bump_varies_the_normal_across_the_surface() {
+        let plane = Object::new_plane().with_bump(Bump::noise_bump(4, 0.2, 3.0, 3));
+
+        let a = plane.normal_at(&Point::new_point3D(0.15, 0.0, 0.35));
+        let b = plane.normal_at(&Point::new_point3D(0.65, 0.0, 0.85));
+
+        assert_ne!(a, b);
+        assert_eq!(true, a[1] > 0.0 && b[1] > 0.0);
+        assert_eq!(true, (a.magnitude() - 1.0).abs() < 1.0e-9);
+    }
+}
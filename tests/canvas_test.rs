@@ -0,0 +1,380 @@
+#[cfg(test)]
+mod canvas_test {
+    use std::io::Cursor;
+
+    use rtc::color::Color;
+    use rtc::loaders::ppm::parse_ppm;
+    use rtc::{Canvas, OutputTransform, PngOptions};
+
+    fn bright_canvas() -> Canvas {
+        let mut canvas = Canvas::new(3, 2);
+        canvas[0][0] = Color::new(4.5, 0.25, 0.0);
+        canvas[0][2] = Color::new(0.0, 1.0, 12.0);
+        canvas[1][1] = Color::new(0.5, 0.75, 1.5);
+        canvas
+    }
+
+    fn output(name: &str) -> String {
+        std::env::temp_dir()
+            .join(name)
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn close(expected: f32, actual: f32, tolerance: f32) -> bool {
+        (expected - actual).abs() <= tolerance * expected.abs().max(1.0)
+    }
+
+    #[test]
+    fn pfm_keeps_the_raw_values() {
+        let file_name = output("rtc_canvas_test.pfm");
+        bright_canvas().convert_to_pfm(&file_name).unwrap();
+        let bytes = std::fs::read(&file_name).unwrap();
+
+        let header = b"PF\n3 2\n-1.0\n";
+        assert_eq!(header.to_vec(), bytes[..header.len()].to_vec());
+        let floats: Vec<f32> = bytes[header.len()..]
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+
+        assert_eq!(18, floats.len());
+        // the bottom row comes first
+        assert_eq!(vec![0.5, 0.75, 1.5], floats[3..6].to_vec());
+        assert_eq!(vec![4.5, 0.25, 0.0], floats[9..12].to_vec());
+        assert_eq!(vec![0.0, 1.0, 12.0], floats[15..18].to_vec());
+    }
+
+    #[test]
+    fn radiance_hdr_keeps_values_above_one() {
+        let file_name = output("rtc_canvas_test.hdr");
+        bright_canvas().convert_to_hdr(&file_name).unwrap();
+        // image::open would turn it into 8 bit colors
+        let file = std::io::BufReader::new(std::fs::File::open(&file_name).unwrap());
+        let decoder = ::image::codecs::hdr::HdrDecoder::new(file).unwrap();
+        let metadata = decoder.metadata();
+        let pixels = decoder.read_image_hdr().unwrap();
+
+        assert_eq!((3, 2), (metadata.width, metadata.height));
+        assert_eq!(true, close(4.5, pixels[0][0], 0.01));
+        // green shares the exponent of red, so it is coarser
+        assert_eq!(true, close(0.25, pixels[0][1], 0.04));
+        assert_eq!(true, close(12.0, pixels[2][2], 0.01));
+        assert_eq!(true, close(1.5, pixels[4][2], 0.01));
+    }
+
+    #[test]
+    fn openexr_keeps_the_raw_values() {
+        let file_name = output("rtc_canvas_test.exr");
+        bright_canvas().convert_to_exr(&file_name).unwrap();
+        let image = ::image::open(&file_name).unwrap().to_rgb32f();
+
+        assert_eq!((3, 2), image.dimensions());
+        assert_eq!([4.5, 0.25, 0.0], image.get_pixel(0, 0).0);
+        assert_eq!([0.5, 0.75, 1.5], image.get_pixel(1, 1).0);
+        assert_eq!([0.0, 1.0, 12.0], image.get_pixel(2, 0).0);
+    }
+
+    fn linear_canvas(width: usize, height: usize) -> Canvas {
+        Canvas::new(width, height).with_output_transform(OutputTransform::new().with_srgb(false))
+    }
+
+    #[test]
+    fn constructing_the_ppm_header() {
+        let mut bytes = Vec::new();
+        linear_canvas(5, 3).write_ppm(&mut bytes).unwrap();
+        let ppm = String::from_utf8(bytes).unwrap();
+
+        assert_eq!(
+            "P3\n5 3\n255\n",
+            ppm.lines().take(3).collect::<Vec<_>>().join("\n") + "\n"
+        );
+    }
+
+    #[test]
+    fn constructing_the_ppm_pixel_data() {
+        let mut canvas = linear_canvas(5, 3);
+        canvas[0][0] = Color::new(1.5, 0.0, 0.0);
+        canvas[1][2] = Color::new(0.0, 0.5, 0.0);
+        canvas[2][4] = Color::new(-0.5, 0.0, 1.0);
+        let mut bytes = Vec::new();
+        canvas.write_ppm(&mut bytes).unwrap();
+        let ppm = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+
+        assert_eq!(
+            vec![
+                "255 0 0 0 0 0 0 0 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 128 0 0 0 0 0 0 0",
+                "0 0 0 0 0 0 0 0 0 0 0 0 0 0 255",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn splitting_long_lines_in_ppm_files() {
+        let mut canvas = linear_canvas(10, 2);
+        for color in canvas.pixels().iter_mut() {
+            *color = Color::new(1.0, 0.8, 0.6);
+        }
+        let mut bytes = Vec::new();
+        canvas.write_ppm(&mut bytes).unwrap();
+        let ppm = String::from_utf8(bytes).unwrap();
+        let lines: Vec<&str> = ppm.lines().skip(3).collect();
+
+        assert_eq!(
+            vec![
+                "255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204",
+                "153 255 204 153 255 204 153 255 204 153 255 204 153",
+                "255 204 153 255 204 153 255 204 153 255 204 153 255 204 153 255 204",
+                "153 255 204 153 255 204 153 255 204 153 255 204 153",
+            ],
+            lines
+        );
+    }
+
+    #[test]
+    fn ppm_files_are_terminated_by_a_newline() {
+        let mut bytes = Vec::new();
+        linear_canvas(5, 3).write_ppm(&mut bytes).unwrap();
+
+        assert_eq!(Some(&b'\n'), bytes.last());
+    }
+
+    #[test]
+    fn writing_a_binary_ppm() {
+        let mut canvas = linear_canvas(2, 1);
+        canvas[0][0] = Color::new(1.0, 0.2, 0.0);
+        canvas[0][1] = Color::new(0.0, 0.0, 0.5);
+        let mut bytes = Vec::new();
+        canvas.write_ppm_binary(&mut bytes).unwrap();
+
+        assert_eq!(b"P6\n2 1\n255\n\xff\x33\x00\x00\x00\x80".to_vec(), bytes);
+        assert_eq!(Color::new(1.0, 0.2, 0.0), parse_ppm(&bytes).unwrap()[0][0]);
+    }
+
+    #[test]
+    fn writing_a_16_bit_png() {
+        let mut canvas = linear_canvas(256, 1);
+        for (i, color) in canvas.pixels().iter_mut().enumerate() {
+            *color = Color::new(i as f64 / 65535.0, 0.5, 1.0);
+        }
+        let mut bytes = Vec::new();
+        canvas
+            .write_png(
+                &mut bytes,
+                PngOptions::new().with_sixteen_bit(true).with_alpha(false),
+            )
+            .unwrap();
+        let image = ::image::load_from_memory(&bytes).unwrap();
+
+        assert_eq!(::image::ColorType::Rgb16, image.color());
+        let image = image.to_rgb16();
+        // the whole gradient fits between two 8 bit levels
+        assert_eq!([0, 32768, 65535], image.get_pixel(0, 0).0);
+        assert_eq!(128, image.get_pixel(128, 0)[0]);
+        assert_eq!(255, image.get_pixel(255, 0)[0]);
+    }
+
+    #[test]
+    fn writing_a_png_with_alpha() {
+        let mut canvas = linear_canvas(1, 1);
+        canvas[0][0] = Color::new(0.0, 1.0, 0.0);
+        let mut bytes = Vec::new();
+        canvas
+            .write_png(&mut bytes, PngOptions::new().with_alpha(true))
+            .unwrap();
+        let image = ::image::load_from_memory(&bytes).unwrap();
+
+        assert_eq!(::image::ColorType::Rgba8, image.color());
+        assert_eq!([0, 255, 0, 255], image.to_rgba8().get_pixel(0, 0).0);
+
+        let mut bytes = Vec::new();
+        canvas
+            .write_png(
+                &mut bytes,
+                PngOptions::new().with_alpha(true).with_sixteen_bit(true),
+            )
+            .unwrap();
+        let image = ::image::load_from_memory(&bytes).unwrap();
+
+        assert_eq!(::image::ColorType::Rgba16, image.color());
+        assert_eq!([0, 65535, 0, 65535], image.to_rgba16().get_pixel(0, 0).0);
+    }
+
+    #[test]
+    fn float_formats_write_to_any_sink() {
+        let canvas = bright_canvas();
+        let mut pfm = Vec::new();
+        canvas.write_pfm(&mut pfm).unwrap();
+        let mut exr = Cursor::new(Vec::new());
+        canvas.write_exr(&mut exr).unwrap();
+        let image = ::image::load_from_memory(exr.get_ref())
+            .unwrap()
+            .to_rgb32f();
+
+        assert_eq!(12 + 3 * 2 * 3 * 4, pfm.len());
+        assert_eq!([0.0, 1.0, 12.0], image.get_pixel(2, 0).0);
+    }
+
+    #[test]
+    fn pixels_are_opaque_by_default() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.set_pixels(vec![Color::black(); 4]);
+
+        assert_eq!(1.0, canvas.alpha_at(0, 0));
+        assert_eq!(1.0, canvas.alpha_at(1, 0));
+        assert_eq!(1.0, canvas.alpha_at(1, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn setting_pixels_of_the_wrong_length() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set_pixels(vec![Color::black(); 4]);
+    }
+
+    #[test]
+    fn pngs_store_straight_colors_and_coverage() {
+        let mut canvas = linear_canvas(3, 1);
+        //half covered, premultiplied
+        canvas[0][0] = Color::new(0.0, 0.4, 0.0);
+        canvas.set_alpha(0, 0, 0.5);
+        canvas[0][1] = Color::new(1.0, 1.0, 1.0);
+        canvas.set_alpha(1, 0, 0.0);
+        let mut bytes = Vec::new();
+        canvas.write_png(&mut bytes, PngOptions::new()).unwrap();
+        let image = ::image::load_from_memory(&bytes).unwrap();
+
+        assert_eq!(::image::ColorType::Rgba8, image.color());
+        let image = image.to_rgba8();
+        assert_eq!([0, 204, 0, 128], image.get_pixel(0, 0).0);
+        assert_eq!([0, 0, 0, 0], image.get_pixel(1, 0).0);
+        assert_eq!([0, 0, 0, 255], image.get_pixel(2, 0).0);
+    }
+
+    #[test]
+    fn compositing_over_a_background() {
+        let mut foreground = Canvas::new(3, 1);
+        foreground[0][0] = Color::new(1.0, 0.0, 0.0);
+        foreground[0][1] = Color::new(0.5, 0.0, 0.0);
+        foreground.set_alpha(1, 0, 0.5);
+        foreground.set_alpha(2, 0, 0.0);
+        let mut background = Canvas::new(3, 1);
+        background.set_pixels(vec![Color::new(0.0, 0.0, 0.5); 3]);
+        background.set_alpha(2, 0, 0.5);
+        let result = foreground.over(&background);
+
+        assert_eq!(Color::new(1.0, 0.0, 0.0), result[0][0]);
+        assert_eq!(1.0, result.alpha_at(0, 0));
+        assert_eq!(Color::new(0.5, 0.0, 0.25), result[0][1]);
+        assert_eq!(1.0, result.alpha_at(1, 0));
+        assert_eq!(Color::new(0.0, 0.0, 0.5), result[0][2]);
+        assert_eq!(0.5, result.alpha_at(2, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn compositing_canvases_of_different_sizes() {
+        Canvas::new(2, 2).over(&Canvas::new(2, 1));
+    }
+
+    fn gradient(width: usize, height: usize) -> Canvas {
+        let mut canvas = Canvas::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let value = (x + y) as f64 / (width + height) as f64;
+                canvas[y][x] = Color::new(value, 1.0 - value, 0.5);
+            }
+        }
+        canvas
+    }
+
+    #[test]
+    fn comparing_identical_canvases() {
+        let a = gradient(10, 8);
+
+        assert_eq!(0.0, a.rmse(&gradient(10, 8)));
+        assert_eq!(f64::INFINITY, a.psnr(&gradient(10, 8)));
+        assert_eq!(0.0, a.max_difference(&gradient(10, 8)));
+        assert_eq!(true, (a.ssim(&gradient(10, 8)) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn comparing_canvases_with_a_constant_offset() {
+        let a = Canvas::new(4, 4);
+        let mut b = Canvas::new(4, 4);
+        b.set_pixels(vec![Color::new(0.1, 0.1, 0.1); 16]);
+
+        assert_eq!(true, (a.rmse(&b) - 0.1).abs() < 1e-12);
+        assert_eq!(true, (a.psnr(&b) - 20.0).abs() < 1e-9);
+        assert_eq!(true, (a.max_difference(&b) - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn the_max_difference_finds_a_single_pixel() {
+        let a = gradient(10, 8);
+        let mut b = gradient(10, 8);
+        b[3][4].green += 0.25;
+
+        assert_eq!(true, (a.max_difference(&b) - 0.25).abs() < 1e-12);
+        assert_eq!(
+            true,
+            (a.rmse(&b) - (0.0625 / 240.0f64).sqrt()).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn ssim_drops_with_structural_changes() {
+        let a = gradient(16, 16);
+        let mut noisy = gradient(16, 16);
+        for (i, color) in noisy.pixels().iter_mut().enumerate() {
+            let offset = if i % 2 == 0 { 0.05 } else { -0.05 };
+            *color = *color + Color::new(offset, offset, offset);
+        }
+        let mut blocky = gradient(16, 16);
+        for (i, color) in blocky.pixels().iter_mut().enumerate() {
+            let offset = if i % 2 == 0 { 0.3 } else { -0.3 };
+            *color = *color + Color::new(offset, offset, offset);
+        }
+        let slight = a.ssim(&noisy);
+        let strong = a.ssim(&blocky);
+
+        assert_eq!(true, slight < 1.0);
+        assert_eq!(true, strong < slight);
+    }
+
+    #[test]
+    fn ssim_of_canvases_smaller_than_the_window() {
+        let a = gradient(3, 2);
+
+        assert_eq!(true, (a.ssim(&gradient(3, 2)) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn the_difference_image_maps_differences_to_colors() {
+        let a = Canvas::new(4, 1);
+        let mut b = Canvas::new(4, 1);
+        b.set_pixels(vec![
+            Color::black(),
+            Color::new(0.25, 0.0, 0.0),
+            Color::new(0.0, 0.5, 0.0),
+            Color::new(0.0, 0.0, 2.0),
+        ]);
+        let diff = a.difference_image(&b, 1.0);
+
+        assert_eq!(Color::black(), diff[0][0]);
+        assert_eq!(Color::new(0.0, 0.0, 1.0), diff[0][1]);
+        assert_eq!(Color::new(0.0, 1.0, 0.0), diff[0][2]);
+        assert_eq!(Color::new(1.0, 0.0, 0.0), diff[0][3]);
+        assert_eq!(Color::new(1.0, 1.0, 0.0), a.difference_image(&b, 1.5)[0][2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comparing_canvases_of_different_sizes() {
+        Canvas::new(2, 2).rmse(&Canvas::new(2, 1));
+    }
+}
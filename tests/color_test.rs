@@ -38,3 +38,24 @@ mod primitive_operations {
         assert_eq!(res, expected);
     }
 }
+
+#[cfg(test)]
+mod channels {
+    use rtc::color::{Channel, Color};
+
+    #[test]
+    fn channel_selects_a_component() {
+        let c = Color::new(0.1, 0.2, 0.3);
+
+        assert_eq!(0.1, c.channel(Channel::Red));
+        assert_eq!(0.2, c.channel(Channel::Green));
+        assert_eq!(0.3, c.channel(Channel::Blue));
+    }
+
+    #[test]
+    fn color_from_a_single_channel() {
+        let expected = Color::new(0.0, 0.5, 0.0);
+
+        assert_eq!(expected, Color::from_channel(Channel::Green, 0.5));
+    }
+}
@@ -0,0 +1,68 @@
+//Helpers shared by the integration tests, a test file uses them with
+// `mod common;`.
+use std::path::PathBuf;
+
+use rtc::loaders::load_png;
+use rtc::loaders::png::parse_png;
+use rtc::{Canvas, PngOptions};
+
+//Set to rewrite the reference images with the current renders.
+const UPDATE: &str = "RTC_UPDATE_REFERENCES";
+
+//Compares `canvas`, as it would be written to an 8 bit PNG, against
+// tests/references/<name>.png and fails when the PSNR drops below
+// `min_psnr` decibels. On failure the render and a difference image are
+// left in the temp directory.
+pub fn assert_matches_reference(canvas: &Canvas, name: &str, min_psnr: f64) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("references")
+        .join(format!("{name}.png"));
+    let options = PngOptions::new().with_alpha(false);
+
+    if std::env::var_os(UPDATE).is_some() {
+        canvas
+            .convert_to_png_with(path.to_str().unwrap(), options)
+            .unwrap();
+        return;
+    }
+
+    let reference = load_png(&path).unwrap_or_else(|error| {
+        panic!(
+            "could not read {}: {:?}, run with {UPDATE}=1 to create it",
+            path.display(),
+            error
+        )
+    });
+    let mut png = Vec::new();
+    canvas.write_png(&mut png, options).unwrap();
+    let actual = parse_png(&png).unwrap();
+    assert_eq!(
+        (reference.width, reference.height),
+        (actual.width, actual.height),
+        "{name} changed size"
+    );
+
+    let psnr = actual.psnr(&reference);
+    if psnr < min_psnr {
+        let dir = std::env::temp_dir();
+        let actual_path = dir.join(format!("{name}_actual.png"));
+        let diff_path = dir.join(format!("{name}_diff.png"));
+        actual
+            .convert_to_png_with(actual_path.to_str().unwrap(), options)
+            .unwrap();
+        actual
+            .difference_image(&reference, 4.0)
+            .convert_to_png_with(diff_path.to_str().unwrap(), options)
+            .unwrap();
+        panic!(
+            "{name} differs from its reference: PSNR {psnr:.2} dB (at least {min_psnr} wanted), \
+             RMSE {:.4}, max difference {:.4}, SSIM {:.4}\nrender: {}\ndifference: {}",
+            actual.rmse(&reference),
+            actual.max_difference(&reference),
+            actual.ssim(&reference),
+            actual_path.display(),
+            diff_path.display()
+        );
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod coverage_test {
+    use std::f64::consts::PI;
+
+    use rtc::color::Color;
+    use rtc::transformation::view_transform;
+    use rtc::{Camera, Point, Vector, World};
+
+    fn camera() -> Camera {
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Point::new_point3D(0.0, 0.0, -5.0);
+        let to = Point::new_point3D(0.0, 0.0, 0.0);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        c.set_transformation(&view_transform(&from, &to, &up));
+        c
+    }
+
+    #[test]
+    fn a_camera_takes_one_sample_by_default() {
+        assert_eq!(1, camera().samples);
+        assert_eq!(3, camera().with_samples(3).samples);
+        assert_eq!(1, camera().with_samples(0).samples);
+    }
+
+    #[test]
+    fn the_center_of_a_subpixel_is_the_pixel_ray() {
+        let c = camera();
+
+        assert_eq!(c.ray_for_pixel(3, 7), c.ray_for_subpixel(3, 7, 0.5, 0.5));
+        assert_eq!(c.ray_for_pixel(4, 8), c.ray_for_subpixel(3, 7, 1.5, 1.5));
+    }
+
+    #[test]
+    fn pixels_that_miss_everything_have_no_coverage() {
+        let w = World::default_test();
+        let image = camera().render(&w);
+
+        assert_eq!(0.0, image.alpha_at(0, 0));
+        assert_eq!(Color::black(), image[0][0]);
+        assert_eq!(1.0, image.alpha_at(5, 5));
+        assert_eq!(w.color_at(&camera().ray_for_pixel(5, 5), 4), image[5][5]);
+    }
+
+    #[test]
+    fn one_sample_gives_either_full_or_no_coverage() {
+        let image = camera().render(&World::default_test());
+
+        for y in 0..11 {
+            for x in 0..11 {
+                let alpha = image.alpha_at(x, y);
+                assert_eq!(true, alpha == 0.0 || alpha == 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn edges_get_fractional_coverage() {
+        let c = camera().with_samples(4);
+        let image = c.render(&World::default_test());
+        let mut edges = 0;
+        for y in 0..11 {
+            for x in 0..11 {
+                let alpha = image.alpha_at(x, y);
+                if alpha > 0.0 && alpha < 1.0 {
+                    edges += 1;
+                    //multiples of 1/16
+                    assert_eq!(0.0, (alpha * 16.0).fract());
+                }
+            }
+        }
+
+        assert_eq!(true, edges > 0);
+        assert_eq!(0.0, image.alpha_at(0, 0));
+        assert_eq!(1.0, image.alpha_at(5, 5));
+    }
+}
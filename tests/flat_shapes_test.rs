@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod flat_shapes_test {
+    use std::f64::consts::PI;
+
+    use rtc::transformation::*;
+    use rtc::{Annulus, ApproximateEq, Disk, LocalShape, Object, Point, Ray, Rectangle, Vector};
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(
+            Point::new_point3D(x, 2.0, z),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        )
+    }
+
+    fn assert_uv(expected: (f64, f64), actual: (f64, f64)) {
+        assert_eq!(true, expected.0.approx_eq(&actual.0), "u {}", actual.0);
+        assert_eq!(true, expected.1.approx_eq(&actual.1), "v {}", actual.1);
+    }
+
+    #[test]
+    fn ray_hitting_a_disk() {
+        let disk = Disk::new(2.0);
+
+        assert_eq!(Some(vec![2.0]), disk.intersect(&down_at(1.0, 1.0)));
+        assert_eq!(Some(vec![2.0]), disk.intersect(&down_at(0.0, -2.0)));
+        assert_eq!(None, disk.intersect(&down_at(1.5, 1.5)));
+    }
+
+    #[test]
+    fn ray_parallel_to_a_disk_misses() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.0, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        assert_eq!(None, Disk::new(1.0).intersect(&ray));
+        assert_eq!(None, Rectangle::new(1.0, 1.0).intersect(&ray));
+        assert_eq!(None, Annulus::new(0.5, 1.0).intersect(&ray));
+    }
+
+    #[test]
+    fn disk_normal_and_uv() {
+        let disk = Disk::new(2.0);
+
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            disk.local_normal_at(&Point::new_point3D(0.5, 0.0, 0.5))
+        );
+        assert_uv(
+            (0.0, 0.5),
+            disk.local_uv(&Point::new_point3D(0.0, 0.0, 1.0)),
+        );
+        assert_uv(
+            (0.25, 1.0),
+            disk.local_uv(&Point::new_point3D(2.0, 0.0, 0.0)),
+        );
+        assert_uv(
+            (0.75, 0.25),
+            disk.local_uv(&Point::new_point3D(-0.5, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn ray_hitting_a_rectangle() {
+        let rectangle = Rectangle::new(2.0, 1.0);
+
+        assert_eq!(Some(vec![2.0]), rectangle.intersect(&down_at(1.9, -0.9)));
+        assert_eq!(Some(vec![2.0]), rectangle.intersect(&down_at(2.0, 1.0)));
+        assert_eq!(None, rectangle.intersect(&down_at(0.0, 1.1)));
+        assert_eq!(None, rectangle.intersect(&down_at(-2.1, 0.0)));
+    }
+
+    #[test]
+    fn rectangle_uv_spans_the_whole_square() {
+        let rectangle = Rectangle::new(2.0, 1.0);
+
+        assert_uv(
+            (0.0, 0.0),
+            rectangle.local_uv(&Point::new_point3D(-2.0, 0.0, -1.0)),
+        );
+        assert_uv(
+            (0.5, 0.5),
+            rectangle.local_uv(&Point::new_point3D(0.0, 0.0, 0.0)),
+        );
+        assert_uv(
+            (0.75, 1.0),
+            rectangle.local_uv(&Point::new_point3D(1.0, 0.0, 1.0)),
+        );
+    }
+
+    #[test]
+    fn ray_hitting_an_annulus() {
+        let annulus = Annulus::new(1.0, 2.0);
+
+        assert_eq!(Some(vec![2.0]), annulus.intersect(&down_at(1.5, 0.0)));
+        assert_eq!(Some(vec![2.0]), annulus.intersect(&down_at(0.0, -1.0)));
+        assert_eq!(None, annulus.intersect(&down_at(0.5, 0.5)));
+        assert_eq!(None, annulus.intersect(&down_at(2.0, 2.0)));
+    }
+
+    #[test]
+    fn annulus_uv() {
+        let annulus = Annulus::new(1.0, 2.0);
+
+        assert_uv(
+            (0.5, 0.5),
+            annulus.local_uv(&Point::new_point3D(0.0, 0.0, -1.5)),
+        );
+        assert_uv(
+            (0.25, 0.0),
+            annulus.local_uv(&Point::new_point3D(1.0, 0.0, 0.0)),
+        );
+    }
+
+    #[test]
+    fn bounds_of_flat_shapes() {
+        let disk = Disk::new(2.0).bounds();
+        let rectangle = Rectangle::new(2.0, 1.0).bounds();
+        let annulus = Annulus::new(1.0, 3.0).bounds();
+
+        assert_eq!(Point::new_point3D(-2.0, 0.0, -2.0), disk.min);
+        assert_eq!(Point::new_point3D(2.0, 0.0, 1.0), rectangle.max);
+        assert_eq!(Point::new_point3D(-3.0, 0.0, -3.0), annulus.min);
+    }
+
+    #[test]
+    fn rectangle_as_a_wall() {
+        let wall = Object::new_rectangle(2.0, 2.0)
+            .with_transformation(translation(0.0, 0.0, 5.0) * rotation_x(PI / 2.0));
+        let hit = Ray::new(
+            Point::new_point3D(1.0, 1.0, 0.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+        let miss = Ray::new(
+            Point::new_point3D(1.0, 3.0, 0.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(Some(vec![5.0]), wall.intersects(&hit));
+        assert_eq!(None, wall.intersects(&miss));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+            wall.normal_at(&Point::new_point3D(1.0, 1.0, 5.0))
+        );
+    }
+
+    #[test]
+    fn objects_with_flat_shapes() {
+        let table = Object::new_disk(1.0).with_transformation(translation(0.0, 1.0, 0.0));
+        let ring = Object::new_annulus(0.5, 1.0);
+
+        assert_eq!(Some(vec![1.0]), table.intersects(&down_at(0.5, 0.0)));
+        assert_eq!(Some(vec![2.0]), ring.intersects(&down_at(0.0, 0.75)));
+        assert_eq!(None, ring.intersects(&down_at(0.0, 0.25)));
+    }
+}
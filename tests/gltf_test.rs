@@ -0,0 +1,514 @@
+#[cfg(test)]
+mod gltf_test {
+    use std::io::Cursor;
+    use std::path::{Path, PathBuf};
+
+    use rtc::color::Color;
+    use rtc::loaders::gltf::parse_gltf;
+    use rtc::loaders::{load_gltf, GltfError};
+    use rtc::{ApproximateEq, Point, Ray, Shape, Vector};
+    use serde_json::{json, Value};
+
+    //Collects vertex data into one binary buffer and writes the matching
+    // buffer views and accessors.
+    struct Builder {
+        bin: Vec<u8>,
+        views: Vec<Value>,
+        accessors: Vec<Value>,
+    }
+
+    impl Builder {
+        fn new() -> Self {
+            Self {
+                bin: Vec::new(),
+                views: Vec::new(),
+                accessors: Vec::new(),
+            }
+        }
+
+        fn view(&mut self, bytes: &[u8]) -> usize {
+            while self.bin.len() % 4 != 0 {
+                self.bin.push(0);
+            }
+            self.views.push(json!({
+                "buffer": 0,
+                "byteOffset": self.bin.len(),
+                "byteLength": bytes.len(),
+            }));
+            self.bin.extend(bytes);
+            self.views.len() - 1
+        }
+
+        fn floats(&mut self, values: &[f32], kind: &str, components: usize) -> usize {
+            let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            let view = self.view(&bytes);
+            self.accessors.push(json!({
+                "bufferView": view,
+                "componentType": 5126,
+                "count": values.len() / components,
+                "type": kind,
+            }));
+            self.accessors.len() - 1
+        }
+
+        fn indices(&mut self, values: &[u16]) -> usize {
+            let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            let view = self.view(&bytes);
+            self.accessors.push(json!({
+                "bufferView": view,
+                "componentType": 5123,
+                "count": values.len(),
+                "type": "SCALAR",
+            }));
+            self.accessors.len() - 1
+        }
+
+        //A unit square on the xz plane, facing up.
+        fn square(&mut self) -> Value {
+            let position = self.floats(
+                &[
+                    -1.0, 0.0, -1.0, 1.0, 0.0, -1.0, 1.0, 0.0, 1.0, -1.0, 0.0, 1.0,
+                ],
+                "VEC3",
+                3,
+            );
+            let normal = self.floats(
+                &[0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+                "VEC3",
+                3,
+            );
+            let uv = self.floats(&[0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0], "VEC2", 2);
+            let indices = self.indices(&[0, 2, 1, 0, 3, 2]);
+            json!({
+                "attributes": { "POSITION": position, "NORMAL": normal, "TEXCOORD_0": uv },
+                "indices": indices,
+                "material": 0,
+            })
+        }
+
+        fn document(&self, buffer: Value, rest: Value) -> Value {
+            let mut document = json!({
+                "asset": { "version": "2.0" },
+                "buffers": [buffer],
+                "bufferViews": self.views,
+                "accessors": self.accessors,
+            });
+            for (key, value) in rest.as_object().unwrap() {
+                document[key] = value.clone();
+            }
+            document
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn glb(json: &Value, bin: &[u8]) -> Vec<u8> {
+        let mut json = serde_json::to_vec(json).unwrap();
+        while json.len() % 4 != 0 {
+            json.push(b' ');
+        }
+        let mut bin = bin.to_vec();
+        while bin.len() % 4 != 0 {
+            bin.push(0);
+        }
+
+        let mut bytes = b"glTF".to_vec();
+        bytes.extend(2u32.to_le_bytes());
+        bytes.extend(((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        bytes.extend((json.len() as u32).to_le_bytes());
+        bytes.extend(b"JSON");
+        bytes.extend(json);
+        bytes.extend((bin.len() as u32).to_le_bytes());
+        bytes.extend(b"BIN\0");
+        bytes.extend(bin);
+        bytes
+    }
+
+    fn base64(bytes: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut encoded = String::new();
+        for chunk in bytes.chunks(3) {
+            let b = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            let bits = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+            for k in 0..4 {
+                if k <= chunk.len() {
+                    encoded.push(ALPHABET[(bits >> (18 - 6 * k) & 63) as usize] as char);
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+        encoded
+    }
+
+    fn png(width: u32, height: u32, pixels: &[[u8; 3]]) -> Vec<u8> {
+        let img = ::image::RgbImage::from_fn(width, height, |x, y| {
+            ::image::Rgb(pixels[(y * width + x) as usize])
+        });
+        let mut bytes = Cursor::new(Vec::new());
+        img.write_to(&mut bytes, ::image::ImageOutputFormat::Png)
+            .unwrap();
+        bytes.into_inner()
+    }
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(
+            Point::new_point3D(x, 10.0, z),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn gltf_with_an_external_buffer_and_a_node_hierarchy() {
+        let mut builder = Builder::new();
+        let square = builder.square();
+        let dir = temp_dir("rtc_gltf_external");
+        std::fs::write(dir.join("square data.bin"), &builder.bin).unwrap();
+        let document = builder.document(
+            json!({ "uri": "square%20data.bin", "byteLength": builder.bin.len() }),
+            json!({
+                "scene": 0,
+                "scenes": [{ "nodes": [0] }],
+                "nodes": [
+                    { "translation": [5.0, 0.0, 0.0], "children": [1] },
+                    { "scale": [2.0, 1.0, 2.0], "mesh": 0 },
+                ],
+                "meshes": [{ "primitives": [square] }],
+                "materials": [{
+                    "pbrMetallicRoughness": {
+                        "baseColorFactor": [0.8, 0.2, 0.1, 1.0],
+                        "metallicFactor": 0.0,
+                        "roughnessFactor": 1.0,
+                    }
+                }],
+            }),
+        );
+        let path = dir.join("scene.gltf");
+        std::fs::write(&path, serde_json::to_vec(&document).unwrap()).unwrap();
+
+        let scene = load_gltf(&path).unwrap();
+        let object = &scene.objects[0];
+
+        assert_eq!(1, scene.objects.len());
+        assert_eq!(Some(vec![10.0]), object.intersects(&down_at(6.5, 1.5)));
+        assert_eq!(None, object.intersects(&down_at(2.5, 0.0)));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            object.normal_at(&Point::new_point3D(6.0, 0.0, 0.0))
+        );
+        assert_eq!(Color::new(0.8, 0.2, 0.1), object.material.color);
+        assert_eq!(0.0, object.material.reflective);
+        assert_eq!(0.0, object.material.specular);
+    }
+
+    #[test]
+    fn node_matrices_and_rotations() {
+        let mut builder = Builder::new();
+        let square = builder.square();
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let document = builder.document(
+            json!({
+                "uri": format!("data:application/octet-stream;base64,{}", base64(&builder.bin)),
+                "byteLength": builder.bin.len(),
+            }),
+            json!({
+                "nodes": [
+                    {
+                        "matrix": [1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+                                   0.0, 0.0, 1.0, 0.0, 0.0, 3.0, 0.0, 1.0],
+                        "children": [1],
+                    },
+                    { "rotation": [0.0, 0.0, half, half], "mesh": 0 },
+                ],
+                "meshes": [{ "primitives": [square] }],
+                "materials": [{}],
+            }),
+        );
+
+        //without scenes every root node is shown
+        let scene = parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new("")).unwrap();
+        let bounds = scene.objects[0].bounds();
+
+        // the square is turned on its side, standing on x = 0
+        assert_eq!(true, bounds.min[0].approx_eq(&0.0));
+        assert_eq!(true, bounds.max[0].approx_eq(&0.0));
+        assert_eq!(true, bounds.min[1].approx_eq(&2.0));
+        assert_eq!(true, bounds.max[1].approx_eq(&4.0));
+        // the default material is a rough white metal
+        assert_eq!(Color::white(), scene.objects[0].material.color);
+    }
+
+    #[test]
+    fn triangle_strips_and_fans() {
+        let mut builder = Builder::new();
+        let position = builder.floats(
+            &[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 1.0],
+            "VEC3",
+            3,
+        );
+        let document = builder.document(
+            json!({
+                "uri": format!("data:application/octet-stream;base64,{}", base64(&builder.bin)),
+                "byteLength": builder.bin.len(),
+            }),
+            json!({
+                "nodes": [{ "mesh": 0 }],
+                "meshes": [{ "primitives": [
+                    { "attributes": { "POSITION": position }, "mode": 5 },
+                    { "attributes": { "POSITION": position }, "mode": 6 },
+                    { "attributes": { "POSITION": position }, "mode": 1 },
+                ] }],
+            }),
+        );
+        let scene = parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new("")).unwrap();
+        let triangles: Vec<Vec<[usize; 3]>> = scene
+            .objects
+            .iter()
+            .map(|object| match &object.shape {
+                Shape::Mesh(mesh) => mesh.triangles().to_vec(),
+                _ => Vec::new(),
+            })
+            .collect();
+
+        // the line primitive has no surface
+        assert_eq!(
+            vec![vec![[0, 1, 2], [1, 3, 2]], vec![[0, 1, 2], [0, 2, 3]]],
+            triangles
+        );
+    }
+
+    #[test]
+    fn glb_with_an_embedded_texture() {
+        let mut builder = Builder::new();
+        let square = builder.square();
+        let image = builder.view(&png(2, 1, &[[255, 0, 0], [0, 0, 255]]));
+        let document = builder.document(
+            json!({ "byteLength": builder.bin.len() }),
+            json!({
+                "nodes": [{ "mesh": 0 }],
+                "meshes": [{ "primitives": [square] }],
+                "materials": [{
+                    "pbrMetallicRoughness": {
+                        "baseColorFactor": [0.5, 1.0, 1.0, 1.0],
+                        "baseColorTexture": { "index": 0 },
+                    }
+                }],
+                "textures": [{ "source": 0, "sampler": 0 }],
+                "samplers": [{ "magFilter": 9728 }],
+                "images": [{ "bufferView": image, "mimeType": "image/png" }],
+            }),
+        );
+        let bytes = glb(&document, &builder.bin);
+
+        let scene = parse_gltf(&bytes, Path::new("")).unwrap();
+        let object = &scene.objects[0];
+
+        // u follows x, so the left half shows the first texel
+        assert_eq!(
+            Some(Color::new(0.5, 0.0, 0.0)),
+            object.pattern_at_object(&Point::new_point3D(-0.5, 0.0, 0.3))
+        );
+        assert_eq!(
+            Some(Color::blue()),
+            object.pattern_at_object(&Point::new_point3D(0.5, 0.0, -0.3))
+        );
+    }
+
+    #[test]
+    fn perspective_cameras() {
+        let document = json!({
+            "asset": { "version": "2.0" },
+            "nodes": [
+                { "translation": [0.0, 0.0, 5.0], "camera": 0 },
+                { "camera": 1 },
+            ],
+            "cameras": [
+                { "type": "perspective", "perspective": { "yfov": 1.0, "aspectRatio": 2.0, "znear": 0.1 } },
+                { "type": "orthographic", "orthographic": { "xmag": 1.0, "ymag": 1.0, "znear": 0.1, "zfar": 10.0 } },
+            ],
+        });
+        let scene = parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new("")).unwrap();
+
+        assert_eq!(1, scene.cameras.len());
+        assert_eq!(Some(2.0), scene.cameras[0].aspect_ratio);
+
+        let camera = scene.cameras[0].camera(200, 100);
+        let center = camera.ray_for_pixel(100, 50);
+        assert_eq!(Point::new_point3D(0.0, 0.0, 5.0), center.origin);
+        assert_eq!(true, center.direction[2] < -0.99);
+
+        // the top left pixel looks up and to the left, towards -x
+        let corner = camera.ray_for_pixel(0, 0);
+        assert_eq!(true, corner.direction[0] < 0.0);
+        assert_eq!(true, corner.direction[1] > 0.0);
+        // yfov is kept between the top and bottom rows
+        let top = camera.ray_for_pixel(100, 0).direction;
+        let bottom = camera.ray_for_pixel(100, 99).direction;
+        assert_eq!(true, (f64::acos(top * bottom) - 1.0).abs() < 0.02);
+    }
+
+    #[test]
+    fn punctual_lights() {
+        let half = std::f64::consts::FRAC_1_SQRT_2;
+        let document = json!({
+            "asset": { "version": "2.0" },
+            "extensionsUsed": ["KHR_lights_punctual"],
+            "extensions": { "KHR_lights_punctual": { "lights": [
+                { "type": "point", "color": [1.0, 0.5, 0.5], "intensity": 2.0 },
+                { "type": "directional" },
+            ] } },
+            "nodes": [
+                { "translation": [1.0, 2.0, 3.0], "extensions": { "KHR_lights_punctual": { "light": 0 } } },
+                { "rotation": [-half, 0.0, 0.0, half], "extensions": { "KHR_lights_punctual": { "light": 1 } } },
+            ],
+        });
+        let scene = parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new("")).unwrap();
+
+        assert_eq!(Color::new(2.0, 1.0, 1.0), scene.lights[0].intensity);
+        assert_eq!(Point::new_point3D(1.0, 2.0, 3.0), scene.lights[0].position);
+        // shining straight down, so it sits high above the scene
+        assert_eq!(Color::white(), scene.lights[1].intensity);
+        assert_eq!(
+            Point::new_point3D(0.0, 1000.0, 0.0),
+            scene.lights[1].position
+        );
+    }
+
+    #[test]
+    fn invalid_files() {
+        let parse =
+            |document: Value| parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new(""));
+
+        assert_eq!(
+            true,
+            matches!(
+                parse(json!({ "asset": { "version": "1.0" } })),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse(json!({
+                    "asset": { "version": "2.0" },
+                    "extensionsRequired": ["KHR_draco_mesh_compression"],
+                })),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse(json!({
+                    "asset": { "version": "2.0" },
+                    "buffers": [{ "uri": "https://example.com/data.bin", "byteLength": 4 }],
+                })),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse(json!({
+                    "asset": { "version": "2.0" },
+                    "nodes": [{ "mesh": 0 }],
+                    "meshes": [{ "primitives": [{ "attributes": { "POSITION": 3 } }] }],
+                })),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_gltf(b"glTF\x02\x00\x00\x00\xff\x00\x00\x00", Path::new("")),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_gltf(b"{ not json", Path::new("")),
+                Err(GltfError::Json(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                load_gltf(temp_dir("rtc_gltf_missing").join("missing.gltf")),
+                Err(GltfError::Io(_))
+            )
+        );
+    }
+
+    #[test]
+    fn accessors_and_views_with_huge_sizes() {
+        let mut builder = Builder::new();
+        builder.floats(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0], "VEC3", 3);
+        let buffer = json!({
+            "uri": format!("data:application/octet-stream;base64,{}", base64(&builder.bin)),
+            "byteLength": builder.bin.len(),
+        });
+        let parse = |position: Value, view: Value| {
+            let mut document = builder.document(
+                buffer.clone(),
+                json!({
+                    "nodes": [{ "mesh": 0 }],
+                    "meshes": [{ "primitives": [{ "attributes": { "POSITION": 1 } }] }],
+                }),
+            );
+            document["accessors"].as_array_mut().unwrap().push(position);
+            document["bufferViews"].as_array_mut().unwrap().push(view);
+            parse_gltf(&serde_json::to_vec(&document).unwrap(), Path::new(""))
+        };
+        let accessor = |count: u64, view: Option<usize>| {
+            let mut accessor = json!({ "componentType": 5126, "count": count, "type": "VEC3" });
+            if let Some(view) = view {
+                accessor["bufferView"] = json!(view);
+            }
+            accessor
+        };
+        let view = json!({ "buffer": 0, "byteLength": 36 });
+        let past_the_end = json!({ "buffer": 0, "byteOffset": u64::MAX, "byteLength": 36 });
+
+        //count * 3 overflows
+        assert_eq!(
+            true,
+            matches!(
+                parse(accessor(u64::MAX / 2, None), view.clone()),
+                Err(GltfError::Format(_))
+            )
+        );
+        //zeros, but far too many of them
+        assert_eq!(
+            true,
+            matches!(
+                parse(accessor(1 << 40, None), view.clone()),
+                Err(GltfError::Format(_))
+            )
+        );
+        //more elements than the view holds
+        assert_eq!(
+            true,
+            matches!(
+                parse(accessor(u64::MAX / 2, Some(1)), view.clone()),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse(accessor(3, Some(1)), past_the_end),
+                Err(GltfError::Format(_))
+            )
+        );
+        assert_eq!(true, parse(accessor(3, Some(1)), view).is_ok());
+    }
+}
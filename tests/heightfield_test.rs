@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod heightfield_test {
+    use rtc::transformation::*;
+    use rtc::{ApproximateEq, Heightfield, LocalShape, Noise, Object, Point, Ray, Vector};
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(
+            Point::new_point3D(x, 5.0, z),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        )
+    }
+
+    fn ramp() -> Heightfield {
+        // rises from 0 at x = -1 to 1 at x = 1
+        Heightfield::from_fn(5, 5, |u, _| u)
+    }
+
+    fn terrain() -> Heightfield {
+        let noise = Noise::new(7);
+        Heightfield::from_fn(33, 33, |u, v| {
+            0.5 + 0.4 * noise.fbm(&Point::new_point3D(u * 4.0, 0.0, v * 4.0), 3)
+        })
+    }
+
+    #[test]
+    fn procedural_samples_span_the_grid() {
+        let field = Heightfield::from_fn(3, 2, |u, v| u + 10.0 * v);
+
+        assert_eq!(3, field.width());
+        assert_eq!(2, field.depth());
+        assert_eq!(0.5, field.sample(1, 0));
+        assert_eq!(11.0, field.sample(2, 1));
+    }
+
+    #[test]
+    fn vertical_ray_on_a_flat_field() {
+        let field = Heightfield::from_fn(4, 4, |_, _| 0.5);
+
+        assert_eq!(Some(vec![4.5]), field.intersect(&down_at(0.3, -0.7)));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            field.normal_at(&Point::new_point3D(0.3, 0.5, -0.7))
+        );
+    }
+
+    #[test]
+    fn rays_outside_of_the_grid_miss() {
+        let field = ramp();
+
+        assert_eq!(None, field.intersect(&down_at(1.5, 0.0)));
+        assert_eq!(
+            None,
+            field.intersect(&Ray::new(
+                Point::new_point3D(-5.0, 2.0, 0.0),
+                Vector::new_vec3D(1.0, 0.0, 0.0)
+            ))
+        );
+        assert_eq!(None, field.height_at(0.0, -1.1));
+    }
+
+    #[test]
+    fn ray_on_a_ramp() {
+        let field = ramp();
+        let hits = field.intersect(&down_at(0.0, 0.25)).unwrap();
+
+        assert_eq!(1, hits.len());
+        assert_eq!(true, hits[0].approx_eq(&4.5));
+        assert_eq!(
+            Vector::new_vec3D(-0.5, 1.0, 0.0).normalize(),
+            field.normal_at(&Point::new_point3D(0.0, 0.5, 0.25))
+        );
+    }
+
+    #[test]
+    fn horizontal_ray_into_a_ramp() {
+        let field = ramp();
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.75, 0.1),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+        let hits = field.intersect(&ray).unwrap();
+
+        // the slope is reached at x = 0.5
+        assert_eq!(true, hits[0].approx_eq(&5.5));
+    }
+
+    #[test]
+    fn traversal_matches_a_brute_force_search() {
+        let field = terrain();
+        let rays = [
+            Ray::new(
+                Point::new_point3D(-3.0, 2.0, -2.5),
+                Vector::new_vec3D(3.0, -1.6, 2.0),
+            ),
+            Ray::new(
+                Point::new_point3D(2.0, 0.9, 1.7),
+                Vector::new_vec3D(-1.0, -0.2, -0.8),
+            ),
+            Ray::new(
+                Point::new_point3D(0.3, 3.0, -3.0),
+                Vector::new_vec3D(0.0, -1.0, 1.0),
+            ),
+            Ray::new(
+                Point::new_point3D(-2.0, 0.55, 0.1),
+                Vector::new_vec3D(1.0, 0.0, 0.01),
+            ),
+        ];
+
+        for ray in rays {
+            let hits = field.intersect(&ray).unwrap_or_default();
+            for t in &hits {
+                let point = ray.position(*t);
+                let height = field.height_at(point[0], point[2]).unwrap();
+                assert_eq!(true, (point[1] - height).abs() < 1.0e-9);
+            }
+
+            // brute force: first sign change of the height above the surface
+            let above = |t: f64| {
+                let point = ray.position(t);
+                field.height_at(point[0], point[2]).map(|h| point[1] - h)
+            };
+            let first_crossing = (0..20000).map(|k| k as f64 * 0.0005).find(|t| {
+                match (above(*t), above(t + 0.0005)) {
+                    (Some(a), Some(b)) => a.signum() != b.signum(),
+                    _ => false,
+                }
+            });
+
+            assert_eq!(first_crossing.is_some(), !hits.is_empty());
+            if let Some(t) = first_crossing {
+                assert_eq!(true, (hits[0] - t).abs() <= 0.0005);
+            }
+        }
+    }
+
+    #[test]
+    fn traversal_agrees_with_sampling_the_surface() {
+        let field = terrain();
+
+        for k in 0..20 {
+            let x = -0.95 + k as f64 * 0.1;
+            let hits = field.intersect(&down_at(x, 0.33)).unwrap();
+            let height = field.height_at(x, 0.33).unwrap();
+
+            assert_eq!(1, hits.len());
+            assert_eq!(true, (5.0 - hits[0] - height).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn normals_are_interpolated_inside_a_triangle() {
+        let field = Heightfield::from_fn(9, 9, |u, _| (u * 3.0).sin());
+        let a = field.normal_at(&Point::new_point3D(0.01, 0.0, 0.0));
+        let b = field.normal_at(&Point::new_point3D(0.2, 0.0, 0.0));
+
+        assert_ne!(a, b);
+        assert_eq!(true, a.magnitude().approx_eq(&1.0));
+        assert_eq!(true, a[1] > 0.0 && b[1] > 0.0);
+    }
+
+    #[test]
+    fn bounds_follow_the_heights() {
+        let bounds = ramp().bounds();
+
+        assert_eq!(Point::new_point3D(-1.0, 0.0, -1.0), bounds.min);
+        assert_eq!(Point::new_point3D(1.0, 1.0, 1.0), bounds.max);
+    }
+
+    #[test]
+    fn loading_a_grayscale_png() {
+        let path = std::env::temp_dir().join("rtc_heightfield_test.png");
+        let img =
+            ::image::GrayImage::from_fn(3, 2, |x, y| ::image::Luma([(x * 100 + y * 50) as u8]));
+        img.save(&path).unwrap();
+
+        let field = Heightfield::load(&path).unwrap();
+
+        assert_eq!(3, field.width());
+        assert_eq!(2, field.depth());
+        assert_eq!(true, field.sample(0, 0).approx_eq(&0.0));
+        assert_eq!(true, field.sample(2, 1).approx_eq(&(250.0 / 255.0)));
+    }
+
+    #[test]
+    fn loading_an_image_that_is_too_small() {
+        let path = std::env::temp_dir().join("rtc_heightfield_line_test.png");
+        ::image::GrayImage::new(4, 1).save(&path).unwrap();
+
+        assert_eq!(
+            true,
+            matches!(
+                Heightfield::load(&path),
+                Err(::image::ImageError::Parameter(_))
+            )
+        );
+        assert_eq!(
+            true,
+            Heightfield::from_image(&::image::DynamicImage::new_luma8(1, 3)).is_err()
+        );
+    }
+
+    #[test]
+    fn scaled_terrain_object() {
+        let object = Object::new_heightfield(ramp()).with_transformation(scaling(10.0, 2.0, 10.0));
+        let ray = Ray::new(
+            Point::new_point3D(5.0, 10.0, 0.0),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        );
+
+        assert_eq!(true, object.intersects(&ray).unwrap()[0].approx_eq(&8.5));
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod material_test {
-    use rtc::{color::Color, Light, Material, Object, Point, Vector};
+    use rtc::{color::Color, ApproximateEq, Light, Material, Object, Point, Vector};
 
     #[test]
     fn eye_opposite_surface() {
@@ -78,4 +78,62 @@ mod material_test {
 
         assert_eq!(expected, res);
     }
+
+    #[test]
+    fn transmittance_decays_exponentially_with_distance() {
+        let mut material = Material::default();
+        material.absorption = Color::new(1.0, 0.0, 0.5);
+
+        let expected = Color::new((-2.0f64).exp(), 1.0, (-1.0f64).exp());
+
+        assert_eq!(expected, material.transmittance(2.0));
+        assert_eq!(Color::white(), material.transmittance(0.0));
+    }
+
+    #[test]
+    fn refractive_index_without_dispersion_is_constant() {
+        let mut material = Material::default();
+        material.refractive_index = 1.5;
+
+        assert_eq!(true, material.refractive_index_at(450.0).approx_eq(&1.5));
+        assert_eq!(true, material.refractive_index_at(650.0).approx_eq(&1.5));
+    }
+
+    #[test]
+    fn refractive_index_follows_the_abbe_number() {
+        let mut material = Material::default();
+        material.refractive_index = 1.5168;
+        material.abbe_number = 64.17;
+
+        let n_d = material.refractive_index_at(587.6);
+        let n_f = material.refractive_index_at(486.1);
+        let n_c = material.refractive_index_at(656.3);
+
+        assert_eq!(true, n_d.approx_eq(&1.5168));
+        assert_eq!(true, ((n_d - 1.0) / (n_f - n_c)).approx_eq(&64.17));
+        assert_eq!(true, material.refractive_index_at(450.0) > n_d);
+        assert_eq!(true, material.refractive_index_at(650.0) < n_d);
+    }
+
+    #[test]
+    fn lightning_through_a_tinted_shadow() {
+        let position = Point::new_point3D(0.0, 0.0, 0.0);
+        let eyev = Vector::new_vec3D(0.0, 0.0, -1.0);
+        let normalv = Vector::new_vec3D(0.0, 0.0, -1.0);
+        let light = Light::new(Color::white(), Point::new_point3D(0.0, 0.0, -10.0));
+
+        let material = Material::default_test();
+
+        let res = material.lightning_with_transmittance(
+            &Object::new_sphere(),
+            &light,
+            &position,
+            &eyev,
+            &normalv,
+            Color::new(0.5, 0.0, 1.0),
+        );
+        let expected = Color::new(0.1 + 0.9, 0.1, 0.1 + 1.8);
+
+        assert_eq!(expected, res);
+    }
 }
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod mesh_test {
+    use rtc::color::Color;
+    use rtc::transformation::*;
+    use rtc::{ApproximateEq, LocalShape, Mesh, Noise, Object, Point, Ray, Vector};
+
+    fn point(x: f64, y: f64, z: f64) -> Point<f64, 4> {
+        Point::new_point3D(x, y, z)
+    }
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(point(x, 5.0, z), Vector::new_vec3D(0.0, -1.0, 0.0))
+    }
+
+    //The square from (0, 0) to (1, 1) on the xz plane.
+    fn square() -> Mesh {
+        Mesh::new(
+            vec![
+                point(0.0, 0.0, 0.0),
+                point(1.0, 0.0, 0.0),
+                point(1.0, 0.0, 1.0),
+                point(0.0, 0.0, 1.0),
+            ],
+            vec![[0, 2, 1], [0, 3, 2]],
+        )
+    }
+
+    #[test]
+    fn ray_through_a_triangle() {
+        let mesh = square();
+
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.7, 0.2)));
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.2, 0.7)));
+        assert_eq!(None, mesh.intersect(&down_at(1.2, 0.5)));
+    }
+
+    #[test]
+    fn a_hit_on_a_shared_edge_is_reported_once() {
+        assert_eq!(Some(vec![5.0]), square().intersect(&down_at(0.5, 0.5)));
+    }
+
+    #[test]
+    fn flat_normals_without_vertex_normals() {
+        let normal = square().normal_at(&point(0.3, 0.0, 0.6));
+
+        assert_eq!(true, normal[1].abs().approx_eq(&1.0));
+    }
+
+    #[test]
+    fn vertex_normals_are_interpolated() {
+        let tilted = Vector::new_vec3D(1.0, 1.0, 0.0).normalize();
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        let mesh = square().with_normals(vec![up, tilted, tilted, up]);
+
+        assert_eq!(up, mesh.normal_at(&point(0.0, 0.0, 0.5)));
+        assert_eq!(tilted, mesh.normal_at(&point(1.0, 0.0, 0.5)));
+        assert_eq!(
+            (up + tilted).normalize(),
+            mesh.normal_at(&point(0.5, 0.0, 0.5))
+        );
+    }
+
+    #[test]
+    fn colors_and_uvs_are_interpolated() {
+        let mesh = square()
+            .with_colors(vec![
+                Color::black(),
+                Color::red(),
+                Color::white(),
+                Color::blue(),
+            ])
+            .with_uvs(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+
+        assert_eq!(
+            Some(Color::new(0.5, 0.0, 0.0)),
+            mesh.color_at(&point(0.5, 0.0, 0.0))
+        );
+        assert_eq!(
+            Some(Color::new(0.5, 0.5, 0.5)),
+            mesh.color_at(&point(0.5, 0.0, 0.5))
+        );
+        assert_eq!((0.25, 0.75), mesh.local_uv(&point(0.25, 0.0, 0.75)));
+        assert_eq!(None, square().color_at(&point(0.5, 0.0, 0.5)));
+    }
+
+    #[test]
+    fn computed_normals_average_the_faces() {
+        // a pyramid, its apex surrounded by four faces
+        let mesh = Mesh::new(
+            vec![
+                point(0.0, 1.0, 0.0),
+                point(1.0, 0.0, 1.0),
+                point(1.0, 0.0, -1.0),
+                point(-1.0, 0.0, -1.0),
+                point(-1.0, 0.0, 1.0),
+            ],
+            vec![[0, 1, 2], [0, 2, 3], [0, 3, 4], [0, 4, 1]],
+        )
+        .with_computed_normals();
+        let apex = mesh.normals().unwrap()[0];
+
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), apex);
+        // a base corner is shared by two faces
+        assert_eq!(
+            Vector::new_vec3D(1.0, 2.0, 1.0).normalize(),
+            mesh.normals().unwrap()[1]
+        );
+    }
+
+    #[test]
+    fn bounds_of_a_mesh() {
+        let bounds = square().bounds();
+
+        assert_eq!(point(0.0, 0.0, 0.0), bounds.min);
+        assert_eq!(point(1.0, 0.0, 1.0), bounds.max);
+    }
+
+    #[test]
+    fn hierarchy_finds_the_same_hits_as_testing_every_triangle() {
+        // a bumpy 40x40 grid, 3200 triangles
+        let noise = Noise::new(3);
+        let size = 41;
+        let mut positions = Vec::new();
+        let mut triangles = Vec::new();
+        for j in 0..size {
+            for i in 0..size {
+                let (x, z) = (i as f64 / 10.0, j as f64 / 10.0);
+                positions.push(point(x, noise.noise(&point(x, 0.0, z)), z));
+            }
+        }
+        for j in 0..size - 1 {
+            for i in 0..size - 1 {
+                let a = j * size + i;
+                triangles.push([a, a + 1, a + size + 1]);
+                triangles.push([a, a + size + 1, a + size]);
+            }
+        }
+        let mesh = Mesh::new(positions.clone(), triangles.clone());
+
+        for k in 0..50 {
+            let ray = Ray::new(
+                point(-1.0, 2.0, k as f64 * 0.08),
+                Vector::new_vec3D(1.0, -0.3, 0.05),
+            );
+            let mut expected: Vec<f64> = triangles
+                .iter()
+                .filter_map(|[a, b, c]| {
+                    Mesh::new(
+                        vec![positions[*a], positions[*b], positions[*c]],
+                        vec![[0, 1, 2]],
+                    )
+                    .intersect(&ray)
+                })
+                .flatten()
+                .collect();
+            expected.sort_by(|a, b| a.total_cmp(b));
+            expected.dedup_by(|a, b| (*a - *b).abs() < 1.0e-9);
+
+            assert_eq!(expected, mesh.intersect(&ray).unwrap_or_default());
+        }
+    }
+
+    #[test]
+    fn mesh_object_with_a_transformation() {
+        let object = Object::new_mesh(square()).with_transformation(scaling(4.0, 1.0, 4.0));
+
+        assert_eq!(Some(vec![5.0]), object.intersects(&down_at(3.5, 3.5)));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            object.normal_at(&point(3.5, 0.0, 3.5))
+        );
+    }
+}
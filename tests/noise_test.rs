@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod noise_test {
+    use rtc::{Noise, Point};
+
+    fn samples() -> Vec<Point<f64, 4>> {
+        (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.37;
+                Point::new_point3D(t, t * 0.61 - 3.0, 5.0 - t * 1.3)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_noise() {
+        let a = Noise::new(7);
+        let b = Noise::new(7);
+
+        for p in samples() {
+            assert_eq!(a.noise(&p), b.noise(&p));
+        }
+    }
+
+    #[test]
+    fn different_seeds_give_different_noise() {
+        let a = Noise::new(1);
+        let b = Noise::new(2);
+
+        let differs = samples().iter().any(|p| a.noise(p) != b.noise(p));
+
+        assert_eq!(true, differs);
+    }
+
+    #[test]
+    fn noise_is_zero_on_lattice_points() {
+        let noise = Noise::new(3);
+
+        assert_eq!(0.0, noise.noise(&Point::new_point3D(0.0, 0.0, 0.0)));
+        assert_eq!(0.0, noise.noise(&Point::new_point3D(4.0, -2.0, 9.0)));
+    }
+
+    #[test]
+    fn noise_is_bounded() {
+        let noise = Noise::new(11);
+
+        for p in samples() {
+            let n = noise.noise(&p);
+            let fbm = noise.fbm(&p, 5);
+            let turbulence = noise.turbulence(&p, 5);
+
+            assert_eq!(true, (-1.0..=1.0).contains(&n));
+            assert_eq!(true, (-1.0..=1.0).contains(&fbm));
+            assert_eq!(true, (0.0..=1.0).contains(&turbulence));
+        }
+    }
+
+    #[test]
+    fn noise_varies_smoothly() {
+        let noise = Noise::new(5);
+        let p = Point::new_point3D(1.3, 2.7, -0.4);
+        let q = Point::new_point3D(1.3001, 2.7, -0.4);
+
+        assert_eq!(true, (noise.noise(&p) - noise.noise(&q)).abs() < 1.0e-3);
+    }
+}
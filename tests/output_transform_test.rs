@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod output_transform_test {
+    use rtc::color::Color;
+    use rtc::output_transform::linear_to_srgb;
+    use rtc::{ApproximateEq, Canvas, OutputTransform, ToneMap};
+
+    fn output(name: &str) -> String {
+        std::env::temp_dir()
+            .join(name)
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn tone_maps() {
+        assert_eq!(1.0, ToneMap::Clamp.map(3.0));
+        assert_eq!(0.0, ToneMap::Clamp.map(-1.0));
+        assert_eq!(0.5, ToneMap::Reinhard.map(1.0));
+        assert_eq!(0.75, ToneMap::Reinhard.map(3.0));
+        assert_eq!(1.0, ToneMap::ExtendedReinhard { white: 4.0 }.map(4.0));
+        assert_eq!(1.0, ToneMap::ExtendedReinhard { white: 4.0 }.map(10.0));
+        assert_eq!(
+            true,
+            ToneMap::ExtendedReinhard { white: 4.0 }
+                .map(1.0)
+                .approx_eq(&0.53125)
+        );
+        assert_eq!(true, ToneMap::Aces.map(1.0).approx_eq(&(2.54 / 3.16)));
+        assert_eq!(1.0, ToneMap::Aces.map(100.0));
+    }
+
+    #[test]
+    fn srgb_encoding() {
+        assert_eq!(0.0, linear_to_srgb(0.0));
+        assert_eq!(true, linear_to_srgb(1.0).approx_eq(&1.0));
+        assert_eq!(true, linear_to_srgb(0.002).approx_eq(&0.02584));
+        assert_eq!(true, (linear_to_srgb(0.5) - 0.735357).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn default_transform_clamps_and_encodes() {
+        let transform = OutputTransform::new();
+
+        assert_eq!(
+            [255, 188, 0],
+            transform.to_rgb8(&Color::new(1.5, 0.5, -0.5))
+        );
+    }
+
+    #[test]
+    fn values_are_rounded() {
+        let linear = OutputTransform::new().with_srgb(false);
+
+        assert_eq!(
+            [128, 127, 1],
+            linear.to_rgb8(&Color::new(0.5, 0.498, 0.003))
+        );
+    }
+
+    #[test]
+    fn exposure_is_in_stops() {
+        let transform = OutputTransform::new().with_srgb(false).with_exposure(1.0);
+
+        assert_eq!(
+            Color::new(0.5, 1.0, 1.0),
+            transform.apply(&Color::new(0.25, 0.5, 2.0))
+        );
+        assert_eq!(
+            Color::new(0.25, 0.5, 1.0),
+            transform
+                .with_exposure(-1.0)
+                .apply(&Color::new(0.5, 1.0, 4.0))
+        );
+    }
+
+    #[test]
+    fn tone_mapping_comes_before_srgb() {
+        let transform = OutputTransform::new().with_tone_map(ToneMap::Reinhard);
+
+        assert_eq!(
+            [188, 188, 188],
+            transform.to_rgb8(&Color::new(1.0, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn ppm_export_uses_the_output_transform() {
+        let mut canvas = Canvas::new(2, 1)
+            .with_output_transform(OutputTransform::new().with_tone_map(ToneMap::Reinhard));
+        canvas[0][0] = Color::new(1.0, 0.0, 3.0);
+        canvas[0][1] = Color::new(0.5, 0.5, 0.5);
+        let file_name = output("rtc_output_transform_test.ppm");
+        canvas.convert_to_ppm(&file_name).unwrap();
+
+        assert_eq!(
+            "P3\n2 1\n255\n188 0 225 156 156 156\n",
+            std::fs::read_to_string(&file_name).unwrap()
+        );
+    }
+
+    #[test]
+    fn png_export_uses_the_output_transform() {
+        let mut canvas = Canvas::new(1, 1);
+        canvas[0][0] = Color::new(0.5, 0.214, 4.0);
+        let file_name = output("rtc_output_transform_test.png");
+        canvas.convert_to_png(&file_name).unwrap();
+        let image = ::image::open(&file_name).unwrap().to_rgb8();
+
+        assert_eq!([188, 127, 255], image.get_pixel(0, 0).0);
+    }
+}
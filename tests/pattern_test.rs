@@ -281,4 +281,663 @@ mod pattern_test {
             );
         }
     }
+
+    mod texture_map {
+        use rtc::{
+            color::Color,
+            pattern::{uv_mapping, CubeFace, UvChecker},
+            Object, Pattern, PatternType, Point, UvMapping, UvPattern,
+        };
+        use std::f64::consts::FRAC_1_SQRT_2;
+
+        #[test]
+        fn checker_pattern_in_2d() {
+            let checkers = UvChecker::new(2.0, 2.0, Color::black(), Color::white());
+
+            assert_eq!(Color::black(), checkers.uv_checker_at(0.0, 0.0));
+            assert_eq!(Color::white(), checkers.uv_checker_at(0.5, 0.0));
+            assert_eq!(Color::white(), checkers.uv_checker_at(0.0, 0.5));
+            assert_eq!(Color::black(), checkers.uv_checker_at(0.5, 0.5));
+            assert_eq!(Color::black(), checkers.uv_checker_at(1.0, 1.0));
+        }
+
+        #[test]
+        fn spherical_mapping_on_a_3d_point() {
+            let cases = [
+                (Point::new_point3D(0.0, 0.0, -1.0), (0.0, 0.5)),
+                (Point::new_point3D(1.0, 0.0, 0.0), (0.25, 0.5)),
+                (Point::new_point3D(0.0, 0.0, 1.0), (0.5, 0.5)),
+                (Point::new_point3D(-1.0, 0.0, 0.0), (0.75, 0.5)),
+                (Point::new_point3D(0.0, 1.0, 0.0), (0.5, 1.0)),
+                (Point::new_point3D(0.0, -1.0, 0.0), (0.5, 0.0)),
+                (
+                    Point::new_point3D(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0),
+                    (0.25, 0.75),
+                ),
+            ];
+
+            for (point, expected) in cases {
+                let (u, v) = uv_mapping::spherical_map(&point);
+                assert_eq!(expected, ((u * 1e5).round() / 1e5, (v * 1e5).round() / 1e5));
+            }
+        }
+
+        #[test]
+        fn planar_mapping_on_a_3d_point() {
+            let cases = [
+                (Point::new_point3D(0.25, 0.0, 0.5), (0.25, 0.5)),
+                (Point::new_point3D(0.25, 0.0, -0.25), (0.25, 0.75)),
+                (Point::new_point3D(0.25, 0.5, -0.25), (0.25, 0.75)),
+                (Point::new_point3D(1.25, 0.0, 0.5), (0.25, 0.5)),
+                (Point::new_point3D(0.25, 0.0, -1.75), (0.25, 0.25)),
+                (Point::new_point3D(1.0, 0.0, -1.0), (0.0, 0.0)),
+                (Point::new_point3D(0.0, 0.0, 0.0), (0.0, 0.0)),
+            ];
+
+            for (point, expected) in cases {
+                assert_eq!(expected, uv_mapping::planar_map(&point));
+            }
+        }
+
+        #[test]
+        fn cylindrical_mapping_on_a_3d_point() {
+            let cases = [
+                (Point::new_point3D(0.0, 0.0, -1.0), (0.0, 0.0)),
+                (Point::new_point3D(0.0, 0.5, -1.0), (0.0, 0.5)),
+                (Point::new_point3D(0.0, 1.0, -1.0), (0.0, 0.0)),
+                (
+                    Point::new_point3D(FRAC_1_SQRT_2, 0.5, -FRAC_1_SQRT_2),
+                    (0.125, 0.5),
+                ),
+                (Point::new_point3D(1.0, 0.5, 0.0), (0.25, 0.5)),
+                (Point::new_point3D(-1.0, 1.25, 0.0), (0.75, 0.25)),
+            ];
+
+            for (point, expected) in cases {
+                let (u, v) = uv_mapping::cylindrical_map(&point);
+                assert_eq!(expected, ((u * 1e5).round() / 1e5, (v * 1e5).round() / 1e5));
+            }
+        }
+
+        #[test]
+        fn layout_of_the_align_check_pattern() {
+            let main = Color::white();
+            let ul = Color::red();
+            let ur = Color::new(1.0, 1.0, 0.0);
+            let bl = Color::green();
+            let br = Color::new(0.0, 1.0, 1.0);
+            let pattern = UvPattern::align_check_pattern(main, ul, ur, bl, br);
+
+            assert_eq!(main, pattern.uv_pattern_at(0.5, 0.5));
+            assert_eq!(ul, pattern.uv_pattern_at(0.1, 0.9));
+            assert_eq!(ur, pattern.uv_pattern_at(0.9, 0.9));
+            assert_eq!(bl, pattern.uv_pattern_at(0.1, 0.1));
+            assert_eq!(br, pattern.uv_pattern_at(0.9, 0.1));
+        }
+
+        #[test]
+        fn identifying_the_face_of_a_cube_from_a_point() {
+            let cases = [
+                (Point::new_point3D(-1.0, 0.5, -0.25), CubeFace::Left),
+                (Point::new_point3D(1.1, -0.75, 0.8), CubeFace::Right),
+                (Point::new_point3D(0.1, 0.6, 0.9), CubeFace::Front),
+                (Point::new_point3D(-0.7, 0.0, -2.0), CubeFace::Back),
+                (Point::new_point3D(0.5, 1.0, 0.9), CubeFace::Up),
+                (Point::new_point3D(-0.2, -1.3, 1.1), CubeFace::Down),
+            ];
+
+            for (point, expected) in cases {
+                assert_eq!(expected, CubeFace::from_point(&point));
+            }
+        }
+
+        #[test]
+        fn uv_mapping_the_faces_of_a_cube() {
+            let cases = [
+                (
+                    CubeFace::Front,
+                    Point::new_point3D(-0.5, 0.5, 1.0),
+                    (0.25, 0.75),
+                ),
+                (
+                    CubeFace::Back,
+                    Point::new_point3D(0.5, -0.5, -1.0),
+                    (0.25, 0.25),
+                ),
+                (
+                    CubeFace::Left,
+                    Point::new_point3D(-1.0, 0.5, -0.5),
+                    (0.25, 0.75),
+                ),
+                (
+                    CubeFace::Right,
+                    Point::new_point3D(1.0, -0.5, 0.5),
+                    (0.25, 0.25),
+                ),
+                (
+                    CubeFace::Up,
+                    Point::new_point3D(-0.5, 1.0, -0.5),
+                    (0.25, 0.75),
+                ),
+                (
+                    CubeFace::Down,
+                    Point::new_point3D(0.5, -1.0, 0.5),
+                    (0.75, 0.75),
+                ),
+            ];
+
+            for (face, point, expected) in cases {
+                assert_eq!(expected, face.uv(&point));
+            }
+        }
+
+        #[test]
+        fn finding_colors_on_a_mapped_cube() {
+            let red = Color::red();
+            let yellow = Color::new(1.0, 1.0, 0.0);
+            let brown = Color::new(1.0, 0.5, 0.0);
+            let green = Color::green();
+            let cyan = Color::new(0.0, 1.0, 1.0);
+            let blue = Color::blue();
+            let purple = Color::new(1.0, 0.0, 1.0);
+            let white = Color::white();
+
+            let left = UvPattern::align_check_pattern(yellow, cyan, red, blue, brown);
+            let front = UvPattern::align_check_pattern(cyan, red, yellow, brown, green);
+            let right = UvPattern::align_check_pattern(red, yellow, purple, green, white);
+            let back = UvPattern::align_check_pattern(green, purple, cyan, white, blue);
+            let up = UvPattern::align_check_pattern(brown, cyan, purple, red, yellow);
+            let down = UvPattern::align_check_pattern(purple, brown, green, blue, white);
+            let pattern = PatternType::cube_map_pattern(left, front, right, back, up, down);
+
+            let cases = [
+                (Point::new_point3D(-1.0, 0.0, 0.0), yellow),
+                (Point::new_point3D(-1.0, 0.9, -0.9), cyan),
+                (Point::new_point3D(-1.0, 0.9, 0.9), red),
+                (Point::new_point3D(0.0, 0.0, 1.0), cyan),
+                (Point::new_point3D(-0.9, 0.9, 1.0), red),
+                (Point::new_point3D(0.9, -0.9, 1.0), green),
+                (Point::new_point3D(1.0, 0.0, 0.0), red),
+                (Point::new_point3D(0.0, 0.0, -1.0), green),
+                (Point::new_point3D(0.0, 1.0, 0.0), brown),
+                (Point::new_point3D(0.0, -1.0, 0.0), purple),
+                (Point::new_point3D(0.9, -1.0, -0.9), white),
+            ];
+
+            for (point, expected) in cases {
+                assert_eq!(Some(expected), pattern.pattern_at(&point));
+            }
+        }
+
+        #[test]
+        fn texture_map_pattern_on_a_sphere() {
+            let checkers = UvPattern::uv_checker_pattern(16.0, 8.0, Color::black(), Color::white());
+            let object = Object::new_sphere().with_pattern(Pattern::with_type(
+                PatternType::texture_map_pattern(checkers, UvMapping::Spherical),
+            ));
+
+            let cases = [
+                (Point::new_point3D(0.4315, 0.4670, 0.7719), Color::white()),
+                (Point::new_point3D(-0.9654, 0.2552, -0.0534), Color::black()),
+                (Point::new_point3D(0.1039, 0.7090, 0.6975), Color::white()),
+                (
+                    Point::new_point3D(-0.4986, -0.7856, -0.3663),
+                    Color::black(),
+                ),
+                (Point::new_point3D(-0.0317, -0.9395, 0.3411), Color::black()),
+                (Point::new_point3D(0.4809, -0.7721, 0.4154), Color::black()),
+                (Point::new_point3D(0.0285, -0.9612, -0.2745), Color::black()),
+                (
+                    Point::new_point3D(-0.5734, -0.2162, -0.7903),
+                    Color::white(),
+                ),
+                (Point::new_point3D(0.7688, -0.1470, 0.6223), Color::black()),
+                (Point::new_point3D(-0.7652, 0.2175, 0.6060), Color::black()),
+            ];
+
+            for (point, expected) in cases {
+                assert_eq!(Some(expected), object.pattern_at_object(&point));
+            }
+        }
+    }
+
+    mod image {
+        use rtc::{
+            color::Color,
+            pattern::image_texture::{Filter, Wrap},
+            ImageTexture, PatternType, Point, UvMapping,
+        };
+
+        //2x2 texture, top row red and green, bottom row blue and white
+        fn texture() -> ImageTexture {
+            ImageTexture::from_colors(
+                2,
+                2,
+                vec![Color::red(), Color::green(), Color::blue(), Color::white()],
+            )
+        }
+
+        #[test]
+        fn nearest_sampling_picks_the_texel_under_uv() {
+            let texture = texture().with_filter(Filter::Nearest);
+
+            assert_eq!(Color::red(), texture.uv_texture_at(0.25, 0.75));
+            assert_eq!(Color::green(), texture.uv_texture_at(0.75, 0.75));
+            assert_eq!(Color::blue(), texture.uv_texture_at(0.25, 0.25));
+            assert_eq!(Color::white(), texture.uv_texture_at(0.75, 0.25));
+        }
+
+        #[test]
+        fn bilinear_sampling_blends_neighbouring_texels() {
+            let texture = texture().with_wrap(Wrap::Clamp);
+
+            assert_eq!(Color::red(), texture.uv_texture_at(0.25, 0.75));
+            assert_eq!(Color::new(0.5, 0.5, 0.5), texture.uv_texture_at(0.5, 0.5));
+            assert_eq!(Color::new(0.5, 0.5, 0.0), texture.uv_texture_at(0.5, 0.75));
+        }
+
+        #[test]
+        fn addressing_outside_the_texture() {
+            let repeat = texture().with_filter(Filter::Nearest);
+            let clamp = repeat.clone().with_wrap(Wrap::Clamp);
+            let mirror = repeat.clone().with_wrap(Wrap::Mirror);
+
+            assert_eq!(Color::red(), repeat.uv_texture_at(1.25, 0.75));
+            assert_eq!(Color::green(), clamp.uv_texture_at(1.25, 0.75));
+            assert_eq!(Color::green(), mirror.uv_texture_at(1.25, 0.75));
+            assert_eq!(Color::red(), mirror.uv_texture_at(1.75, 0.75));
+        }
+
+        #[test]
+        fn clones_share_the_same_texels() {
+            let texture = texture();
+
+            assert_eq!(texture, texture.clone());
+            assert_ne!(texture, self::texture());
+        }
+
+        #[test]
+        fn loading_an_srgb_png_gives_linear_colors() {
+            let path = std::env::temp_dir().join("rtc_image_texture_test.png");
+            let img = ::image::RgbImage::from_pixel(1, 1, ::image::Rgb([255, 128, 0]));
+            img.save(&path).unwrap();
+
+            let texture = ImageTexture::load(&path).unwrap();
+            let linear = ImageTexture::load_linear(&path).unwrap();
+
+            assert_eq!(
+                Color::new(1.0, 0.21586, 0.0),
+                texture.uv_texture_at(0.5, 0.5)
+            );
+            assert_eq!(
+                Color::new(1.0, 0.50196, 0.0),
+                linear.uv_texture_at(0.5, 0.5)
+            );
+        }
+
+        #[test]
+        #[should_panic]
+        fn a_texture_without_texels() {
+            ImageTexture::from_colors(0, 0, vec![]);
+        }
+
+        #[test]
+        fn empty_images_are_rejected() {
+            let empty = ::image::DynamicImage::new_rgb8(0, 0);
+
+            assert_eq!(
+                true,
+                matches!(
+                    ImageTexture::from_image(&empty, true),
+                    Err(::image::ImageError::Parameter(_))
+                )
+            );
+        }
+
+        #[test]
+        fn image_pattern_through_a_planar_mapping() {
+            let pattern = PatternType::image_pattern(
+                texture().with_filter(Filter::Nearest),
+                UvMapping::Planar,
+            );
+
+            assert_eq!(
+                Some(Color::blue()),
+                pattern.pattern_at(&Point::new_point3D(0.25, 0.0, 0.25))
+            );
+            assert_eq!(
+                Some(Color::green()),
+                pattern.pattern_at(&Point::new_point3D(1.75, 0.0, 0.75))
+            );
+        }
+    }
+
+    mod perturbed {
+        use rtc::{color::Color, PatternType, Point};
+
+        fn stripes() -> PatternType {
+            PatternType::striped_pattern(Color::white(), Color::black())
+        }
+
+        #[test]
+        fn perturbed_pattern_without_scale_is_the_inner_pattern() {
+            let pattern = PatternType::perturbed_pattern(stripes(), 0.0, 42);
+
+            for i in 0..20 {
+                let p = Point::new_point3D(i as f64 * 0.33, 0.5, -1.2);
+                assert_eq!(stripes().pattern_at(&p), pattern.pattern_at(&p));
+            }
+        }
+
+        #[test]
+        fn perturbed_pattern_moves_the_stripe_borders() {
+            let pattern = PatternType::perturbed_pattern(stripes(), 0.5, 42);
+
+            let moved = (0..50)
+                .map(|i| Point::new_point3D(i as f64 * 0.13, 0.4, 0.7))
+                .any(|p| stripes().pattern_at(&p) != pattern.pattern_at(&p));
+
+            assert_eq!(true, moved);
+        }
+
+        #[test]
+        fn perturbed_pattern_is_reproducible_from_its_seed() {
+            let a = PatternType::perturbed_pattern(stripes(), 0.5, 9);
+            let b = PatternType::perturbed_pattern(stripes(), 0.5, 9);
+
+            assert_eq!(a, b);
+            for i in 0..50 {
+                let p = Point::new_point3D(i as f64 * 0.13, 0.4, 0.7);
+                assert_eq!(a.pattern_at(&p), b.pattern_at(&p));
+            }
+        }
+    }
+
+    mod nested {
+        use rtc::{
+            color::Color,
+            pattern::{Checker, Striped},
+            transformation::scaling,
+            Pattern, PatternType, Point,
+        };
+
+        fn red_stripes() -> PatternType {
+            PatternType::striped_pattern(Color::red(), Color::white())
+        }
+
+        fn blue_stripes() -> PatternType {
+            PatternType::striped_pattern(Color::blue(), Color::black())
+        }
+
+        #[test]
+        fn checker_of_stripes() {
+            let pattern = Checker::new(red_stripes(), blue_stripes());
+
+            // first square, first and second stripe
+            assert_eq!(
+                Color::red(),
+                pattern.checker_at(&Point::new_point3D(0.5, 0.0, 0.0))
+            );
+            assert_eq!(
+                Color::black(),
+                pattern.checker_at(&Point::new_point3D(1.5, 0.0, 0.0))
+            );
+            assert_eq!(
+                Color::blue(),
+                pattern.checker_at(&Point::new_point3D(0.5, 0.0, 1.5))
+            );
+        }
+
+        #[test]
+        fn nested_patterns_use_their_own_transformation() {
+            let thin =
+                Pattern::with_type(red_stripes()).with_transformation(scaling(0.5, 1.0, 1.0));
+            let pattern = Striped::new(thin, Color::green());
+
+            assert_eq!(
+                Color::red(),
+                pattern.stripe_at(&Point::new_point3D(0.25, 0.0, 0.0))
+            );
+            assert_eq!(
+                Color::white(),
+                pattern.stripe_at(&Point::new_point3D(0.75, 0.0, 0.0))
+            );
+            assert_eq!(
+                Color::green(),
+                pattern.stripe_at(&Point::new_point3D(1.25, 0.0, 0.0))
+            );
+        }
+
+        #[test]
+        fn blended_pattern_averages_both_patterns() {
+            let pattern = PatternType::blended_pattern(red_stripes(), blue_stripes());
+
+            assert_eq!(
+                Some(Color::new(0.5, 0.0, 0.5)),
+                pattern.pattern_at(&Point::new_point3D(0.5, 0.0, 0.0))
+            );
+            assert_eq!(
+                Some(Color::new(0.5, 0.5, 0.5)),
+                pattern.pattern_at(&Point::new_point3D(1.5, 0.0, 0.0))
+            );
+        }
+
+        #[test]
+        fn masked_pattern_selects_by_the_mask() {
+            let mask = PatternType::checker_pattern(Color::black(), Color::white());
+            let pattern = PatternType::masked_pattern(mask, Color::red(), blue_stripes());
+
+            assert_eq!(
+                Some(Color::red()),
+                pattern.pattern_at(&Point::new_point3D(0.5, 0.0, 0.0))
+            );
+            assert_eq!(
+                Some(Color::black()),
+                pattern.pattern_at(&Point::new_point3D(1.5, 0.0, 0.0))
+            );
+
+            let gray =
+                PatternType::masked_pattern(Color::new(0.5, 0.5, 0.5), Color::red(), Color::blue());
+            assert_eq!(
+                Some(Color::new(0.5, 0.0, 0.5)),
+                gray.pattern_at(&Point::new_point3D(0.0, 0.0, 0.0))
+            );
+        }
+
+        #[test]
+        fn additive_pattern_sums_both_patterns() {
+            let pattern = PatternType::additive_pattern(red_stripes(), blue_stripes());
+
+            assert_eq!(
+                Some(Color::new(1.0, 0.0, 1.0)),
+                pattern.pattern_at(&Point::new_point3D(0.5, 0.0, 0.0))
+            );
+            assert_eq!(
+                Some(Color::white()),
+                pattern.pattern_at(&Point::new_point3D(1.5, 0.0, 0.0))
+            );
+        }
+    }
+
+    mod surface {
+        use rtc::{
+            color::Color, pattern::image_texture::Filter, transformation::scaling, ImageTexture,
+            Mesh, Object, Pattern, PatternType, Point, SurfacePoint, UvMapping,
+        };
+
+        //The square from (0, 0) to (2, 2) on the xz plane, u going along z
+        // and v along x, unlike the planar mapping.
+        fn square(pattern: Pattern) -> Object {
+            let mesh = Mesh::new(
+                vec![
+                    Point::new_point3D(0.0, 0.0, 0.0),
+                    Point::new_point3D(2.0, 0.0, 0.0),
+                    Point::new_point3D(2.0, 0.0, 2.0),
+                    Point::new_point3D(0.0, 0.0, 2.0),
+                ],
+                vec![[0, 2, 1], [0, 3, 2]],
+            )
+            .with_uvs(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)]);
+            Object::new_mesh(mesh).with_pattern(pattern)
+        }
+
+        //2x2 texture, top row red and green, bottom row blue and white
+        fn texture() -> PatternType {
+            let texture = ImageTexture::from_colors(
+                2,
+                2,
+                vec![Color::red(), Color::green(), Color::blue(), Color::white()],
+            )
+            .with_filter(Filter::Nearest);
+            PatternType::image_pattern(texture, UvMapping::Surface)
+        }
+
+        //uv (0.25, 0.75), the planar mapping would give (0.5, 0.5)
+        fn point() -> Point<f64, 4> {
+            Point::new_point3D(1.5, 0.0, 0.5)
+        }
+
+        #[test]
+        fn the_surface_mapping_reads_the_surface_point() {
+            let p = Point::new_point3D(0.25, 0.0, 0.5);
+            let object = square(Pattern::with_type(PatternType::None));
+            let surface = SurfacePoint::new(&object.shape, point());
+
+            assert_eq!(
+                None,
+                UvMapping::Surface.map_on_surface(&p, &SurfacePoint::none())
+            );
+            assert_eq!(
+                Some((0.25, 0.75)),
+                UvMapping::Surface.map_on_surface(&p, &surface)
+            );
+            assert_eq!(
+                Some((0.25, 0.5)),
+                UvMapping::Planar.map_on_surface(&p, &surface)
+            );
+            assert_eq!(Some(Color::black()), texture().pattern_at(&p));
+        }
+
+        #[test]
+        #[should_panic]
+        fn the_surface_mapping_has_no_uv_of_its_own() {
+            UvMapping::Surface.map(&Point::new_point3D(0.25, 0.0, 0.5));
+        }
+
+        #[test]
+        fn surface_texture_on_an_object() {
+            let object = square(Pattern::with_type(texture()));
+
+            assert_eq!(Some(Color::red()), object.pattern_at_object(&point()));
+        }
+
+        #[test]
+        fn surface_texture_nested_in_a_slot() {
+            let blended = PatternType::blended_pattern(texture(), Color::black());
+            let object = square(Pattern::with_type(blended));
+
+            assert_eq!(
+                Some(Color::new(0.5, 0.0, 0.0)),
+                object.pattern_at_object(&point())
+            );
+        }
+
+        #[test]
+        fn surface_texture_through_a_perturbed_pattern() {
+            let perturbed = PatternType::perturbed_pattern(texture(), 0.0, 1);
+            let object = square(Pattern::with_type(perturbed));
+
+            assert_eq!(Some(Color::red()), object.pattern_at_object(&point()));
+        }
+
+        #[test]
+        fn the_pattern_transformation_leaves_surface_uv_alone() {
+            let pattern = Pattern::with_type(texture()).with_transformation(scaling(0.5, 0.5, 1.0));
+            let object = square(pattern);
+
+            assert_eq!(Some(Color::red()), object.pattern_at_object(&point()));
+        }
+    }
+
+    mod custom {
+        use rtc::{
+            color::Color,
+            pattern::Striped,
+            transformation::{scaling, translation},
+            LocalPattern, Object, Pattern, PatternType, Point,
+        };
+
+        #[derive(Debug)]
+        struct TestPattern;
+
+        impl LocalPattern for TestPattern {
+            fn local_pattern_at(&self, point: &Point<f64, 4>) -> Color {
+                Color::new(point[0], point[1], point[2])
+            }
+        }
+
+        fn test_pattern() -> Pattern {
+            Pattern::with_type(PatternType::custom_pattern(TestPattern))
+        }
+
+        #[test]
+        fn custom_pattern_with_an_object_transformation() {
+            let mut object = Object::new_sphere().with_transformation(scaling(2.0, 2.0, 2.0));
+            object.material.pattern = test_pattern();
+
+            assert_eq!(
+                Some(Color::new(1.0, 1.5, 2.0)),
+                object.pattern_at_object(&Point::new_point3D(2.0, 3.0, 4.0))
+            );
+        }
+
+        #[test]
+        fn custom_pattern_with_a_pattern_transformation() {
+            let mut object = Object::new_sphere();
+            object.material.pattern = test_pattern().with_transformation(scaling(2.0, 2.0, 2.0));
+
+            assert_eq!(
+                Some(Color::new(1.0, 1.5, 2.0)),
+                object.pattern_at_object(&Point::new_point3D(2.0, 3.0, 4.0))
+            );
+        }
+
+        #[test]
+        fn custom_pattern_with_both_transformations() {
+            let mut object = Object::new_sphere().with_transformation(scaling(2.0, 2.0, 2.0));
+            object.material.pattern =
+                test_pattern().with_transformation(translation(0.5, 1.0, 1.5));
+
+            assert_eq!(
+                Some(Color::new(0.75, 0.5, 0.25)),
+                object.pattern_at_object(&Point::new_point3D(2.5, 3.0, 3.5))
+            );
+        }
+
+        #[test]
+        fn custom_pattern_nested_in_a_builtin_pattern() {
+            let pattern = PatternType::striped_pattern(
+                PatternType::custom_pattern(TestPattern),
+                Color::black(),
+            );
+
+            assert_eq!(
+                Some(Color::new(0.5, 0.25, 0.0)),
+                pattern.pattern_at(&Point::new_point3D(0.5, 0.25, 0.0))
+            );
+        }
+
+        #[test]
+        fn builtin_patterns_implement_the_trait() {
+            let stripes = Striped::new(Color::white(), Color::black());
+            let patterns: Vec<&dyn LocalPattern> = vec![&stripes, &TestPattern];
+            let point = Point::new_point3D(1.5, 0.0, 0.0);
+
+            assert_eq!(Color::black(), patterns[0].local_pattern_at(&point));
+            assert_eq!(
+                Color::new(1.5, 0.0, 0.0),
+                patterns[1].local_pattern_at(&point)
+            );
+        }
+    }
 }
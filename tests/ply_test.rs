@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod ply_test {
+    use rtc::color::Color;
+    use rtc::loaders::ply::parse_ply;
+    use rtc::loaders::{load_ply, MeshError};
+    use rtc::{Light, Material, Object, Point, Ray, Vector};
+
+    const ASCII: &str = "ply
+format ascii 1.0
+comment a unit square, split in two
+element vertex 4
+property float x
+property float y
+property float z
+property uchar red
+property uchar green
+property uchar blue
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 255 0 0
+1 0 0 0 255 0
+1 0 1 0 0 255
+0   0 1   255 255 255
+4 0 3 2 1
+";
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(
+            Point::new_point3D(x, 5.0, z),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        )
+    }
+
+    //Header for a binary file holding a single triangle with normals.
+    fn binary_header(format: &str) -> Vec<u8> {
+        format!(
+            "ply\nformat {} 1.0\nelement vertex 3\n\
+             property float x\nproperty float y\nproperty float z\n\
+             property double nx\nproperty double ny\nproperty double nz\n\
+             element face 1\nproperty list uchar uint vertex_index\n\
+             element edge 1\nproperty int vertex1\nproperty int vertex2\nend_header\n",
+            format
+        )
+        .into_bytes()
+    }
+
+    fn binary(
+        format: &str,
+        f32_bytes: fn(f32) -> [u8; 4],
+        f64_bytes: fn(f64) -> [u8; 8],
+        u32_bytes: fn(u32) -> [u8; 4],
+    ) -> Vec<u8> {
+        let mut bytes = binary_header(format);
+        for [x, y, z] in [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]] {
+            for value in [x, y, z] {
+                bytes.extend(f32_bytes(value));
+            }
+            for value in [0.0, 1.0, 0.0] {
+                bytes.extend(f64_bytes(value));
+            }
+        }
+        bytes.push(3);
+        for index in [0, 2, 1] {
+            bytes.extend(u32_bytes(index));
+        }
+        bytes.extend(u32_bytes(0));
+        bytes.extend(u32_bytes(1));
+        bytes
+    }
+
+    #[test]
+    fn ascii_ply_with_colors() {
+        let mesh = parse_ply(ASCII.as_bytes()).unwrap();
+
+        assert_eq!(4, mesh.positions().len());
+        assert_eq!(2, mesh.triangles().len());
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.25, 0.75)));
+        assert_eq!(
+            Some(Color::red()),
+            mesh.color_at(&Point::new_point3D(0.0, 0.0, 0.0))
+        );
+        assert_eq!(
+            Some(Color::new(0.5, 0.5, 0.0)),
+            mesh.color_at(&Point::new_point3D(0.5, 0.0, 0.0))
+        );
+        assert_eq!(None, mesh.normals());
+    }
+
+    #[test]
+    fn binary_little_endian_ply_with_normals() {
+        let bytes = binary(
+            "binary_little_endian",
+            f32::to_le_bytes,
+            f64::to_le_bytes,
+            u32::to_le_bytes,
+        );
+        let mesh = parse_ply(&bytes).unwrap();
+
+        assert_eq!(vec![[0, 2, 1]], mesh.triangles().to_vec());
+        assert_eq!(Point::new_point3D(1.0, 0.0, 0.0), mesh.positions()[1]);
+        assert_eq!(Vector::new_vec3D(0.0, 1.0, 0.0), mesh.normals().unwrap()[2]);
+    }
+
+    #[test]
+    fn binary_big_endian_ply() {
+        let bytes = binary(
+            "binary_big_endian",
+            f32::to_be_bytes,
+            f64::to_be_bytes,
+            u32::to_be_bytes,
+        );
+        let mesh = parse_ply(&bytes).unwrap();
+
+        assert_eq!(Point::new_point3D(0.0, 0.0, 1.0), mesh.positions()[2]);
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.2, 0.2)));
+    }
+
+    #[test]
+    fn float_colors_and_texture_coordinates() {
+        let source = "ply\nformat ascii 1.0\nelement vertex 3\n\
+            property float x\nproperty float y\nproperty float z\n\
+            property float red\nproperty float green\nproperty float blue\n\
+            property float s\nproperty float t\n\
+            element face 1\nproperty list uchar int vertex_indices\nend_header\n\
+            0 0 0 0.5 0.5 0.5 0 0\n1 0 0 0.5 0.5 0.5 1 0\n0 0 1 0.5 0.5 0.5 0 1\n3 0 1 2\n";
+        let mesh = parse_ply(source.as_bytes()).unwrap();
+
+        assert_eq!(Color::new(0.5, 0.5, 0.5), mesh.colors().unwrap()[1]);
+        assert_eq!((1.0, 0.0), mesh.uvs().unwrap()[1]);
+    }
+
+    #[test]
+    fn malformed_ply_files() {
+        let missing_data = ASCII.replace("4 0 3 2 1\n", "");
+        let missing_vertex = ASCII.replace("4 0 3 2 1", "3 0 1 9");
+
+        assert_eq!(
+            true,
+            matches!(parse_ply(b"obj\n"), Err(MeshError::Format(_)))
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_ply(missing_data.as_bytes()),
+                Err(MeshError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_ply(missing_vertex.as_bytes()),
+                Err(MeshError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_ply(&binary_header("binary_little_endian")),
+                Err(MeshError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(
+                load_ply(std::env::temp_dir().join("rtc_missing.ply")),
+                Err(MeshError::Io(_))
+            )
+        );
+    }
+
+    #[test]
+    fn counts_and_indices_must_be_whole_numbers() {
+        for face in [
+            "4 0 3 2 -1",
+            "4 0 3 2 1.5",
+            "4 0 3 NaN 1",
+            "-4 0 3 2 1",
+            "4.5 0 3 2 1",
+        ] {
+            let ply = ASCII.replace("4 0 3 2 1", face);
+            assert_eq!(
+                true,
+                matches!(parse_ply(ply.as_bytes()), Err(MeshError::Format(_)))
+            );
+        }
+    }
+
+    #[test]
+    fn huge_counts_over_a_truncated_body() {
+        let huge_vertices = ASCII.replace("element vertex 4", "element vertex 4000000000000000000");
+        //the three vertices, then a face claiming u32::MAX indices
+        let header = String::from_utf8(binary_header("binary_little_endian"))
+            .unwrap()
+            .replace("list uchar uint", "list uint uint");
+        let mut huge_face = header.into_bytes();
+        huge_face.extend(vec![0; 3 * 36]);
+        huge_face.extend(u32::MAX.to_le_bytes());
+
+        assert_eq!(
+            true,
+            matches!(
+                parse_ply(huge_vertices.as_bytes()),
+                Err(MeshError::Format(_))
+            )
+        );
+        assert_eq!(
+            true,
+            matches!(parse_ply(&huge_face), Err(MeshError::Format(_)))
+        );
+    }
+
+    #[test]
+    fn vertex_colors_drive_the_material_color() {
+        let path = std::env::temp_dir().join("rtc_ply_test.ply");
+        std::fs::write(&path, ASCII).unwrap();
+        let mut object = Object::new_mesh(load_ply(&path).unwrap());
+        object.material = Material::default_test();
+        object.material.ambient = 1.0;
+        object.material.diffuse = 0.0;
+        object.material.specular = 0.0;
+        let light = Light::new(Color::white(), Point::new_point3D(0.0, 10.0, 0.0));
+        let eyev = Vector::new_vec3D(0.0, 1.0, 0.0);
+        let normalv = Vector::new_vec3D(0.0, 1.0, 0.0);
+
+        let corner = Point::new_point3D(1.0, 0.0, 1.0);
+        let color = object
+            .material
+            .lightning(&object, &light, &corner, &eyev, &normalv, false);
+        assert_eq!(Color::blue(), color);
+
+        object.material.color = Color::new(0.5, 0.5, 0.5);
+        let color = object
+            .material
+            .lightning(&object, &light, &corner, &eyev, &normalv, false);
+        assert_eq!(Color::new(0.0, 0.0, 0.5), color);
+    }
+}
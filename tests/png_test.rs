@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod png_test {
+    use rtc::color::Color;
+    use rtc::loaders::png::parse_png;
+    use rtc::loaders::{load_png, CanvasError};
+    use rtc::Canvas;
+
+    #[test]
+    fn reading_an_8_bit_png() {
+        let path = std::env::temp_dir().join("rtc_png_test_8.png");
+        ::image::RgbImage::from_fn(2, 3, |x, y| ::image::Rgb([x as u8 * 255, y as u8 * 51, 0]))
+            .save(&path)
+            .unwrap();
+
+        let canvas = load_png(&path).unwrap();
+
+        assert_eq!(2, canvas.width);
+        assert_eq!(3, canvas.height);
+        assert_eq!(Color::new(1.0, 0.4, 0.0), canvas[2][1]);
+        assert_eq!(Color::new(0.0, 0.2, 0.0), canvas[1][0]);
+    }
+
+    #[test]
+    fn reading_a_16_bit_gray_png_with_alpha() {
+        let path = std::env::temp_dir().join("rtc_png_test_16.png");
+        ::image::ImageBuffer::<::image::LumaA<u16>, Vec<u16>>::from_fn(1, 1, |_, _| {
+            ::image::LumaA([16384, 0])
+        })
+        .save(&path)
+        .unwrap();
+
+        let canvas = load_png(&path).unwrap();
+        let value = 16384.0 / 65535.0;
+
+        assert_eq!(Color::new(value, value, value), canvas[0][0]);
+    }
+
+    #[test]
+    fn reading_back_a_written_png() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas[0][0] = Color::new(1.0, 0.0, 0.5);
+        let path = std::env::temp_dir().join("rtc_png_test_written.png");
+        canvas.convert_to_png(path.to_str().unwrap()).unwrap();
+
+        let read = load_png(&path).unwrap();
+
+        // the default output transform stores sRGB values
+        assert_eq!(Color::new(1.0, 0.0, 188.0 / 255.0), read[0][0]);
+        assert_eq!(Color::black(), read[0][1]);
+    }
+
+    #[test]
+    fn invalid_png_data() {
+        assert_eq!(
+            true,
+            matches!(parse_png(b"P3 1 1 255 0 0 0"), Err(CanvasError::Image(_)))
+        );
+        assert_eq!(
+            true,
+            matches!(
+                load_png(std::env::temp_dir().join("rtc_missing.png")),
+                Err(CanvasError::Io(_))
+            )
+        );
+    }
+}
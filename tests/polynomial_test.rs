@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod polynomial_test {
+    use rtc::utils::polynomial::*;
+    use rtc::ApproximateEq;
+
+    fn assert_roots(expected: &[f64], roots: &[f64]) {
+        assert_eq!(expected.len(), roots.len(), "roots: {:?}", roots);
+        for (e, r) in expected.iter().zip(roots) {
+            assert_eq!(true, e.approx_eq(r), "expected {} got {}", e, r);
+        }
+    }
+
+    #[test]
+    fn linear_and_quadratic() {
+        assert_roots(&[2.0], &real_roots(&[0.0, 0.0, 2.0, -4.0]));
+        assert_roots(&[-3.0, 2.0], &solve_quadratic(1.0, 1.0, -6.0));
+        assert_roots(&[], &solve_quadratic(1.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn quadratic_without_cancellation() {
+        // roots 1e-8 and 1e8, the naive formula loses the small one
+        let roots = solve_quadratic(1.0, -(1.0e8 + 1.0e-8), 1.0);
+
+        assert_eq!(true, (roots[0] - 1.0e-8).abs() < 1.0e-20);
+        assert_eq!(true, (roots[1] - 1.0e8).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn cubic_with_three_roots() {
+        // (x + 1)(x - 2)(x - 5)
+        assert_roots(&[-1.0, 2.0, 5.0], &solve_cubic(1.0, -6.0, 3.0, 10.0));
+    }
+
+    #[test]
+    fn cubic_with_a_triple_root_crosses_once_per_critical_point() {
+        // (x - 1)^3
+        let roots = solve_cubic(1.0, -3.0, 3.0, -1.0);
+
+        assert_eq!(true, roots.iter().all(|r| r.approx_eq(&1.0)));
+    }
+
+    #[test]
+    fn quartic_with_four_roots() {
+        // (x - 1)(x - 2)(x - 3)(x - 4)
+        assert_roots(
+            &[1.0, 2.0, 3.0, 4.0],
+            &solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0),
+        );
+    }
+
+    #[test]
+    fn quartic_with_double_roots() {
+        // (x - 1)^2 (x - 2)^2
+        assert_roots(
+            &[1.0, 1.0, 2.0, 2.0],
+            &solve_quartic(1.0, -6.0, 13.0, -12.0, 4.0),
+        );
+    }
+
+    #[test]
+    fn quartic_without_real_roots() {
+        // (x^2 + 1)^2
+        assert_roots(&[], &solve_quartic(1.0, 0.0, 2.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn quartic_with_close_roots() {
+        // (x - 1)(x - 1.0001)(x + 3)(x + 7)
+        let coefficients = [1.0, 7.9999, 1.9991, -32.0011, 21.0021];
+        let roots = real_roots(&coefficients);
+
+        assert_eq!(4, roots.len());
+        for root in roots {
+            assert_eq!(true, evaluate(&coefficients, root).abs() < 1.0e-9);
+        }
+    }
+
+    #[test]
+    fn derivative_of_a_quartic() {
+        assert_eq!(
+            vec![4.0, -30.0, 70.0, -50.0],
+            derivative(&[1.0, -10.0, 35.0, -50.0, 24.0])
+        );
+    }
+}
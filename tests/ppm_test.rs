@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod ppm_test {
+    use rtc::color::Color;
+    use rtc::loaders::ppm::parse_ppm;
+    use rtc::loaders::{load_ppm, CanvasError};
+    use rtc::{Canvas, OutputTransform};
+
+    #[test]
+    fn reading_a_file_with_the_wrong_magic_number() {
+        let ppm = "P32\n1 1\n255\n0 0 0\n";
+
+        assert_eq!(
+            true,
+            matches!(parse_ppm(ppm.as_bytes()), Err(CanvasError::Format(_)))
+        );
+    }
+
+    #[test]
+    fn reading_a_ppm_returns_a_canvas_of_the_right_size() {
+        let ppm = "P3\n10 2\n255\n\
+            0 0 0  0 0 0  0 0 0  0 0 0  0 0 0\n\
+            0 0 0  0 0 0  0 0 0  0 0 0  0 0 0\n\
+            0 0 0  0 0 0  0 0 0  0 0 0  0 0 0\n\
+            0 0 0  0 0 0  0 0 0  0 0 0  0 0 0\n";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        assert_eq!(10, canvas.width);
+        assert_eq!(2, canvas.height);
+    }
+
+    #[test]
+    fn reading_pixel_data_from_a_ppm_file() {
+        let ppm = "P3\n4 3\n255\n\
+            255 127 0  0 127 255  127 255 0  255 255 255\n\
+            0 0 0  255 0 0  0 255 0  0 0 255\n\
+            255 255 0  0 255 255  255 0 255  127 127 127\n";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        let cases = [
+            (0, 0, Color::new(1.0, 0.498, 0.0)),
+            (1, 0, Color::new(0.0, 0.498, 1.0)),
+            (2, 0, Color::new(0.498, 1.0, 0.0)),
+            (3, 0, Color::new(1.0, 1.0, 1.0)),
+            (0, 1, Color::new(0.0, 0.0, 0.0)),
+            (1, 1, Color::new(1.0, 0.0, 0.0)),
+            (2, 1, Color::new(0.0, 1.0, 0.0)),
+            (3, 1, Color::new(0.0, 0.0, 1.0)),
+            (0, 2, Color::new(1.0, 1.0, 0.0)),
+            (1, 2, Color::new(0.0, 1.0, 1.0)),
+            (2, 2, Color::new(1.0, 0.0, 1.0)),
+            (3, 2, Color::new(0.498, 0.498, 0.498)),
+        ];
+        for (x, y, color) in cases {
+            let pixel = canvas[y][x];
+            assert_eq!(true, (color.red - pixel.red).abs() < 0.001);
+            assert_eq!(true, (color.green - pixel.green).abs() < 0.001);
+            assert_eq!(true, (color.blue - pixel.blue).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn ppm_parsing_ignores_comment_lines() {
+        let ppm = "P3\n# this is a comment\n2 1\n# this, too\n255\n\
+            # another comment\n255 255 255\n# oh, no, comments in the pixel data!\n\
+            255 0 255\n";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), canvas[0][0]);
+        assert_eq!(Color::new(1.0, 0.0, 1.0), canvas[0][1]);
+    }
+
+    #[test]
+    fn ppm_parsing_allows_an_rgb_triple_to_span_lines() {
+        let ppm = "P3\n1 1\n255\n51\n153\n\n204\n";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        assert_eq!(Color::new(0.2, 0.6, 0.8), canvas[0][0]);
+    }
+
+    #[test]
+    fn ppm_parsing_respects_the_scale_setting() {
+        let ppm = "P3\n2 2\n100\n100 100 100  50 50 50\n75 50 25  0 0 0\n";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        assert_eq!(Color::new(0.75, 0.5, 0.25), canvas[1][0]);
+    }
+
+    #[test]
+    fn odd_whitespace_and_comments_after_values() {
+        let ppm = "P3\t2\r\n1#size\n 255\n\n\t 0   255 0#first\r\n0 0\t\t255";
+        let canvas = parse_ppm(ppm.as_bytes()).unwrap();
+
+        assert_eq!(Color::new(0.0, 1.0, 0.0), canvas[0][0]);
+        assert_eq!(Color::new(0.0, 0.0, 1.0), canvas[0][1]);
+    }
+
+    #[test]
+    fn reading_a_raw_ppm() {
+        let mut ppm = b"P6\n# raw data follows\n2 1\n255\n".to_vec();
+        // the first byte looks like whitespace but is already pixel data
+        ppm.extend([10, 51, 255, 0, 128, 0]);
+        let canvas = parse_ppm(&ppm).unwrap();
+
+        assert_eq!(Color::new(10.0 / 255.0, 0.2, 1.0), canvas[0][0]);
+        assert_eq!(Color::new(0.0, 128.0 / 255.0, 0.0), canvas[0][1]);
+    }
+
+    #[test]
+    fn reading_a_raw_ppm_with_16_bit_values() {
+        let mut ppm = b"P6 1 1 1000\n".to_vec();
+        for value in [1000u16, 500, 250] {
+            ppm.extend(value.to_be_bytes());
+        }
+        let canvas = parse_ppm(&ppm).unwrap();
+
+        assert_eq!(Color::new(1.0, 0.5, 0.25), canvas[0][0]);
+    }
+
+    #[test]
+    fn malformed_ppm_files() {
+        let errors = [
+            "P3\n2 1\n255\n0 0 0 0 0\n".as_bytes(),
+            "P3\n1 1\n255\n0 256 0\n".as_bytes(),
+            "P3\n1 1\n0\n0 0 0\n".as_bytes(),
+            "P3\nwide 1\n255\n0 0 0\n".as_bytes(),
+            b"P6\n2 1\n255\n\x00\x01\x02",
+            b"P6\n1 1\n255#c\n\x00\x01\x02",
+        ];
+        for ppm in errors {
+            assert_eq!(true, matches!(parse_ppm(ppm), Err(CanvasError::Format(_))));
+        }
+        assert_eq!(
+            true,
+            matches!(
+                load_ppm(std::env::temp_dir().join("rtc_missing.ppm")),
+                Err(CanvasError::Io(_))
+            )
+        );
+    }
+
+    #[test]
+    fn ppm_sizes_that_overflow() {
+        let errors = [
+            b"P6\n4294967295 4294967295\n255\n\x00\x00\x00".as_slice(),
+            b"P6\n4294967295 1431655765\n65535\n\x00\x00\x00".as_slice(),
+            b"P3\n4294967295 4294967295\n255\n0 0 0\n".as_slice(),
+        ];
+        for ppm in errors {
+            assert_eq!(true, matches!(parse_ppm(ppm), Err(CanvasError::Format(_))));
+        }
+    }
+
+    #[test]
+    fn reading_back_a_written_ppm() {
+        let mut canvas =
+            Canvas::new(3, 2).with_output_transform(OutputTransform::new().with_srgb(false));
+        canvas[0][0] = Color::new(1.0, 0.2, 0.6);
+        canvas[1][2] = Color::new(0.0, 0.4, 0.8);
+        let path = std::env::temp_dir().join("rtc_ppm_test.ppm");
+        canvas.convert_to_ppm(path.to_str().unwrap()).unwrap();
+
+        let read = load_ppm(&path).unwrap();
+
+        assert_eq!(3, read.width);
+        assert_eq!(Color::new(1.0, 0.2, 0.6), read[0][0]);
+        assert_eq!(Color::new(0.0, 0.4, 0.8), read[1][2]);
+    }
+}
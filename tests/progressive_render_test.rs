@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod progressive_render_test {
+    use std::f64::consts::PI;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, OnceLock};
+
+    use rtc::bump::Bump;
+    use rtc::color::Color;
+    use rtc::transformation::view_transform;
+    use rtc::{Camera, Point, ProgressiveRender, Vector, World};
+
+    fn camera() -> Camera {
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Point::new_point3D(0.0, 0.0, -5.0);
+        let to = Point::new_point3D(0.0, 0.0, 0.0);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        c.set_transformation(&view_transform(&from, &to, &up));
+        c
+    }
+
+    #[test]
+    fn nothing_is_rendered_before_the_first_pass() {
+        let c = camera();
+        let w = World::default_test();
+        let render = ProgressiveRender::new(&c, &w);
+        let image = render.estimate();
+
+        assert_eq!(0, render.passes());
+        assert_eq!(Color::black(), image[5][5]);
+        assert_eq!(0.0, image.alpha_at(5, 5));
+    }
+
+    #[test]
+    fn the_first_pass_matches_a_plain_render() {
+        let c = camera();
+        let w = World::default_test();
+        let mut render = c.render_progressive(&w);
+        render.pass();
+        let image = render.estimate();
+        let expected = c.render(&w);
+
+        assert_eq!(1, render.passes());
+        for y in 0..11 {
+            for x in 0..11 {
+                assert_eq!(expected[y][x], image[y][x]);
+                assert_eq!(expected.alpha_at(x, y), image.alpha_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn iterating_yields_an_estimate_per_pass() {
+        let c = camera();
+        let w = World::default_test();
+        let mut render = c.render_progressive(&w);
+        let estimates: Vec<_> = render.by_ref().take(3).collect();
+
+        assert_eq!(3, estimates.len());
+        assert_eq!(3, render.passes());
+    }
+
+    #[test]
+    fn the_callback_stops_the_render() {
+        let c = camera();
+        let w = World::default_test();
+        let mut seen = Vec::new();
+        let image = c.render_progressive(&w).run(|_, passes| {
+            seen.push(passes);
+            passes < 4
+        });
+
+        assert_eq!(vec![1, 2, 3, 4], seen);
+        assert_eq!(1.0, image.alpha_at(5, 5));
+        assert_eq!(0.0, image.alpha_at(0, 0));
+    }
+
+    #[test]
+    fn passes_refine_the_image() {
+        let c = camera();
+        let w = World::default_test();
+        let reference = c.with_samples(8).render(&w);
+        let mut render = c.render_progressive(&w);
+        let first = render.next().unwrap();
+        let refined = render.nth(14).unwrap();
+
+        assert_eq!(16, render.passes());
+        assert_eq!(true, refined.rmse(&reference) < first.rmse(&reference));
+        let edges = (0..11)
+            .flat_map(|y| (0..11).map(move |x| (x, y)))
+            .filter(|&(x, y)| {
+                let alpha = refined.alpha_at(x, y);
+                alpha > 0.0 && alpha < 1.0
+            })
+            .count();
+        assert_eq!(true, edges > 0);
+    }
+
+    static CANCEL: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+    //A flat height that cancels the render the first time it is shaded.
+    fn cancel_while_shading(_: &Point<f64, 4>) -> f64 {
+        CANCEL.get().unwrap().store(true, Ordering::Relaxed);
+        0.0
+    }
+
+    #[test]
+    fn cancelling_stops_a_pass_part_way() {
+        let c = camera();
+        let mut w = World::default_test();
+        w.objects[0].material.bump = Bump::height_bump(cancel_while_shading, 1.0);
+        let cancel = CANCEL.get_or_init(|| Arc::new(AtomicBool::new(false)));
+        let mut render = c.render_progressive(&w).with_cancel(cancel.clone());
+
+        assert_eq!(false, render.pass());
+        assert_eq!(true, cancel.load(Ordering::Relaxed));
+        assert_eq!(0, render.passes());
+        assert_eq!(Color::black(), render.estimate()[5][5]);
+        assert_eq!(0.0, render.estimate().alpha_at(5, 5));
+        assert_eq!(true, render.next().is_none());
+    }
+
+    #[test]
+    fn a_cancelled_render_keeps_the_passes_so_far() {
+        let c = camera();
+        let w = World::default_test();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut seen = Vec::new();
+        let image = c
+            .render_progressive(&w)
+            .with_cancel(cancel.clone())
+            .run(|_, passes| {
+                seen.push(passes);
+                if passes == 2 {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+                true
+            });
+
+        assert_eq!(vec![1, 2], seen);
+        assert_eq!(1.0, image.alpha_at(5, 5));
+        assert_eq!(0.0, image.alpha_at(0, 0));
+    }
+}
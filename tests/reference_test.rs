@@ -0,0 +1,59 @@
+mod common;
+
+#[cfg(test)]
+mod reference_test {
+    use std::f64::consts::PI;
+
+    use rtc::color::Color;
+    use rtc::transformation::{scaling, translation, view_transform};
+    use rtc::{Camera, Object, Pattern, PatternType, Point, Vector, World};
+
+    use super::common::assert_matches_reference;
+
+    fn camera(hsize: usize, vsize: usize, from: Point<f64, 4>) -> Camera {
+        let mut c = Camera::new(hsize, vsize, PI / 3.0);
+        let to = Point::new_point3D(0.0, 1.0, 0.0);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        c.set_transformation(&view_transform(&from, &to, &up));
+        c
+    }
+
+    #[test]
+    fn default_world() {
+        let mut c = Camera::new(48, 32, PI / 2.0).with_samples(2);
+        let from = Point::new_point3D(0.0, 0.0, -5.0);
+        let to = Point::new_point3D(0.0, 0.0, 0.0);
+        let up = Vector::new_vec3D(0.0, 1.0, 0.0);
+        c.set_transformation(&view_transform(&from, &to, &up));
+        let image = c.render(&World::default_test());
+
+        assert_matches_reference(&image, "default_world", 40.0);
+    }
+
+    #[test]
+    fn reflective_and_glass_spheres_on_a_checker_floor() {
+        let mut w = World::default();
+        let mut mirror = Object::new_sphere()
+            .with_color(Color::new(0.1, 0.1, 0.1))
+            .with_transformation(translation(-0.8, 1.0, 0.5));
+        mirror.material.reflective = 0.9;
+        let mut glass = Object::new_sphere()
+            .with_color(Color::new(0.1, 0.1, 0.1))
+            .with_transformation(translation(1.0, 0.5, -0.8) * scaling(0.5, 0.5, 0.5));
+        glass.material.transparency = 0.9;
+        glass.material.refractive_index = 1.5;
+        let striped = Object::new_sphere()
+            .with_color(Color::white())
+            .with_transformation(translation(0.6, 0.35, 1.2) * scaling(0.35, 0.35, 0.35))
+            .with_pattern(Pattern::with_type(PatternType::striped_pattern(
+                Color::new(0.9, 0.2, 0.1),
+                Color::new(0.1, 0.2, 0.9),
+            )));
+        w.push_object(mirror);
+        w.push_object(glass);
+        w.push_object(striped);
+        let image = camera(64, 40, Point::new_point3D(0.0, 2.0, -5.0)).render(&w);
+
+        assert_matches_reference(&image, "spheres_on_a_checker_floor", 35.0);
+    }
+}
@@ -0,0 +1,262 @@
+#[cfg(test)]
+mod sdf_test {
+    use rtc::shapes::SdfShape;
+    use rtc::transformation::*;
+    use rtc::{ApproximateEq, Object, Point, Ray, Sdf, Vector};
+
+    fn point(x: f64, y: f64, z: f64) -> Point<f64, 4> {
+        Point::new_point3D(x, y, z)
+    }
+
+    fn ray_along_z(x: f64, y: f64) -> Ray {
+        Ray::new(point(x, y, -5.0), Vector::new_vec3D(0.0, 0.0, 1.0))
+    }
+
+    fn close(expected: f64, actual: f64) -> bool {
+        (expected - actual).abs() < 1.0e-5
+    }
+
+    #[test]
+    fn primitive_distances() {
+        assert_eq!(
+            true,
+            Sdf::sphere(1.0)
+                .distance(&point(0.0, 3.0, 0.0))
+                .approx_eq(&2.0)
+        );
+        assert_eq!(
+            true,
+            Sdf::sphere(1.0)
+                .distance(&point(0.0, 0.0, 0.0))
+                .approx_eq(&-1.0)
+        );
+
+        let cuboid = Sdf::cuboid(1.0, 2.0, 3.0);
+        assert_eq!(true, cuboid.distance(&point(2.0, 0.0, 0.0)).approx_eq(&1.0));
+        assert_eq!(
+            true,
+            cuboid
+                .distance(&point(2.0, 3.0, 0.0))
+                .approx_eq(&2.0f64.sqrt())
+        );
+        assert_eq!(
+            true,
+            cuboid.distance(&point(0.5, 0.0, 0.0)).approx_eq(&-0.5)
+        );
+
+        let torus = Sdf::torus(1.0, 0.25);
+        assert_eq!(true, torus.distance(&point(0.0, 0.0, 0.0)).approx_eq(&0.75));
+        assert_eq!(
+            true,
+            torus.distance(&point(1.0, 0.0, 0.0)).approx_eq(&-0.25)
+        );
+
+        let cylinder = Sdf::cylinder(1.0, 1.0);
+        assert_eq!(
+            true,
+            cylinder.distance(&point(0.0, 3.0, 0.0)).approx_eq(&2.0)
+        );
+        assert_eq!(
+            true,
+            cylinder.distance(&point(3.0, 0.0, 0.0)).approx_eq(&2.0)
+        );
+
+        let capsule = Sdf::capsule(0.5, 1.0);
+        assert_eq!(
+            true,
+            capsule.distance(&point(0.0, 2.0, 0.0)).approx_eq(&0.5)
+        );
+        assert_eq!(
+            true,
+            capsule.distance(&point(1.0, 0.7, 0.0)).approx_eq(&0.5)
+        );
+    }
+
+    #[test]
+    fn transformed_distances() {
+        let moved = Sdf::sphere(1.0).translated(2.0, 0.0, 0.0);
+        assert_eq!(true, moved.distance(&point(2.0, 0.0, 0.0)).approx_eq(&-1.0));
+
+        let scaled = Sdf::sphere(1.0).scaled(2.0);
+        assert_eq!(true, scaled.distance(&point(0.0, 0.0, 5.0)).approx_eq(&3.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn scaling_by_a_negative_factor() {
+        Sdf::sphere(1.0).scaled(-2.0);
+    }
+
+    #[test]
+    fn boolean_operators() {
+        let a = Sdf::sphere(1.0);
+        let b = Sdf::sphere(1.0).translated(1.5, 0.0, 0.0);
+        let p = point(0.25, 0.0, 0.0);
+
+        assert_eq!(
+            true,
+            a.clone().union(b.clone()).distance(&p).approx_eq(&-0.75)
+        );
+        assert_eq!(
+            true,
+            a.clone()
+                .intersection(b.clone())
+                .distance(&p)
+                .approx_eq(&0.25)
+        );
+        assert_eq!(true, a.clone().subtraction(b).distance(&p).approx_eq(&-0.25));
+    }
+
+    #[test]
+    fn smooth_operators_blend_only_near_both_shapes() {
+        let a = Sdf::sphere(1.0);
+        let b = Sdf::sphere(1.0).translated(3.0, 0.0, 0.0);
+        let union = a.clone().union(b.clone());
+        let smooth = a.smooth_union(b, 0.5);
+
+        // in the gap between the spheres the blend pulls the surface in
+        let gap = point(1.5, 0.0, 0.0);
+        assert_eq!(true, smooth.distance(&gap) < union.distance(&gap));
+        // far from the other sphere it is the plain union
+        let far = point(-3.0, 0.0, 0.0);
+        assert_eq!(true, smooth.distance(&far).approx_eq(&union.distance(&far)));
+    }
+
+    #[test]
+    fn smooth_subtraction_and_intersection_match_sharp_ones_far_away() {
+        let a = Sdf::cuboid(1.0, 1.0, 1.0);
+        let b = Sdf::sphere(0.5).translated(0.0, 1.0, 0.0);
+        let p = point(0.0, -3.0, 0.0);
+
+        assert_eq!(
+            true,
+            a.clone()
+                .smooth_subtraction(b.clone(), 0.1)
+                .distance(&p)
+                .approx_eq(&a.clone().subtraction(b.clone()).distance(&p))
+        );
+        assert_eq!(
+            true,
+            a.clone()
+                .smooth_intersection(b.clone(), 0.1)
+                .distance(&p)
+                .approx_eq(&a.intersection(b).distance(&p))
+        );
+    }
+
+    #[test]
+    fn marching_a_sphere_matches_the_analytic_hits() {
+        let shape = SdfShape::new(Sdf::sphere(1.0));
+        let hits = shape.intersect(&ray_along_z(0.0, 0.0)).unwrap();
+
+        assert_eq!(2, hits.len());
+        assert_eq!(true, close(4.0, hits[0]));
+        assert_eq!(true, close(6.0, hits[1]));
+    }
+
+    #[test]
+    fn marching_past_a_shape_misses() {
+        let shape = SdfShape::new(Sdf::sphere(1.0));
+
+        assert_eq!(None, shape.intersect(&ray_along_z(1.5, 0.0)));
+        assert_eq!(None, shape.intersect(&ray_along_z(0.9, 0.9)));
+    }
+
+    #[test]
+    fn a_shallow_ray_crosses_the_surface_once_each_way() {
+        let shape = SdfShape::new(Sdf::sphere(1.0));
+        let hits = shape.intersect(&ray_along_z(0.99, 0.0)).unwrap();
+        let half_chord = (1.0f64 - 0.99 * 0.99).sqrt();
+
+        assert_eq!(2, hits.len());
+        assert_eq!(true, close(5.0 - half_chord, hits[0]));
+        assert_eq!(true, close(5.0 + half_chord, hits[1]));
+    }
+
+    #[test]
+    fn a_ray_grazing_the_surface_does_not_cross_it() {
+        let shape = SdfShape::new(Sdf::sphere(1.0));
+
+        assert_eq!(None, shape.intersect(&ray_along_z(1.0, 0.0)));
+    }
+
+    #[test]
+    fn marching_through_a_hollowed_box() {
+        let shape = SdfShape::new(Sdf::cuboid(1.0, 1.0, 1.0).subtraction(Sdf::sphere(0.5)));
+        let hits = shape.intersect(&ray_along_z(0.0, 0.0)).unwrap();
+
+        assert_eq!(4, hits.len());
+        for (expected, t) in [4.0, 4.5, 5.5, 6.0].iter().zip(hits) {
+            assert_eq!(true, close(*expected, t));
+        }
+    }
+
+    #[test]
+    fn smooth_union_fills_the_gap_between_shapes() {
+        let a = Sdf::sphere(1.0).translated(-1.1, 0.0, 0.0);
+        let b = Sdf::sphere(1.0).translated(1.1, 0.0, 0.0);
+        let ray = Ray::new(point(0.0, 5.0, 0.0), Vector::new_vec3D(0.0, -1.0, 0.0));
+
+        assert_eq!(
+            None,
+            SdfShape::new(a.clone().union(b.clone())).intersect(&ray)
+        );
+        assert_eq!(
+            true,
+            SdfShape::new(a.smooth_union(b, 1.0))
+                .intersect(&ray)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn normals_come_from_the_gradient() {
+        let shape = SdfShape::new(Sdf::sphere(1.0));
+        let diagonal = 3.0f64.sqrt() / 3.0;
+
+        assert_eq!(
+            Vector::new_vec3D(diagonal, diagonal, diagonal),
+            shape.normal_at(&point(diagonal, diagonal, diagonal))
+        );
+
+        let cuboid = SdfShape::new(Sdf::cuboid(1.0, 1.0, 1.0));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 0.0, -1.0),
+            cuboid.normal_at(&point(0.3, -0.2, -1.0))
+        );
+    }
+
+    #[test]
+    fn marching_a_mandelbulb() {
+        let shape = SdfShape::new(Sdf::mandelbulb(8.0, 10));
+        let hits = shape.intersect(&ray_along_z(0.0, 0.0)).unwrap();
+
+        assert_eq!(true, hits[0] > 3.8 && hits[0] < 5.0);
+        assert_eq!(
+            true,
+            shape
+                .normal_at(
+                    &Ray::new(point(0.0, 0.0, -5.0), Vector::new_vec3D(0.0, 0.0, 1.0))
+                        .position(hits[0])
+                )
+                .magnitude()
+                .approx_eq(&1.0)
+        );
+    }
+
+    #[test]
+    fn sdf_object_with_a_transformation() {
+        let object = Object::new_sdf(Sdf::torus(1.0, 0.25))
+            .with_transformation(translation(0.0, 1.0, 0.0) * scaling(2.0, 2.0, 2.0));
+        let ray = Ray::new(point(-10.0, 1.0, 0.0), Vector::new_vec3D(1.0, 0.0, 0.0));
+        let hits = object.intersects(&ray).unwrap();
+
+        assert_eq!(4, hits.len());
+        assert_eq!(true, close(7.5, hits[0]));
+        assert_eq!(true, close(12.5, hits[3]));
+        assert_eq!(
+            Vector::new_vec3D(-1.0, 0.0, 0.0),
+            object.normal_at(&point(-2.5, 1.0, 0.0))
+        );
+    }
+}
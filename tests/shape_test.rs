@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod shape_test {
+    use std::sync::Mutex;
+
+    use rtc::shapes::Plane;
+    use rtc::transformation::*;
+    use rtc::{ApproximateEq, Bounds, LocalShape, Object, Point, Ray, Shape, Sphere, Vector};
+
+    //Records the last ray it was asked to intersect, in object space.
+    #[derive(Debug, Default)]
+    struct TestShape {
+        saved_ray: Mutex<Option<Ray>>,
+    }
+
+    impl LocalShape for TestShape {
+        fn local_intersect(&self, ray: &Ray) -> Option<Vec<f64>> {
+            *self.saved_ray.lock().unwrap() = Some(*ray);
+            Some(vec![1.0])
+        }
+
+        fn local_normal_at(&self, point: &Point<f64, 4>) -> Vector<f64, 4> {
+            Vector::new_vec3D(point[0], point[1], point[2])
+        }
+
+        fn bounds(&self) -> Bounds {
+            Bounds::new(
+                Point::new_point3D(-1.0, -1.0, -1.0),
+                Point::new_point3D(1.0, 1.0, 1.0),
+            )
+        }
+
+        fn local_uv(&self, point: &Point<f64, 4>) -> (f64, f64) {
+            (point[0], point[2])
+        }
+    }
+
+    #[test]
+    fn intersecting_a_scaled_custom_shape() {
+        let shape = std::sync::Arc::new(TestShape::default());
+        let object = Object::new(
+            Shape::Custom(rtc::shapes::CustomShape(shape.clone())),
+            scaling(2.0, 2.0, 2.0),
+        );
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(Some(vec![1.0]), object.intersects(&ray));
+
+        let saved_ray = shape.saved_ray.lock().unwrap().unwrap();
+        assert_eq!(Point::new_point3D(0.0, 0.0, -2.5), saved_ray.origin);
+        assert_eq!(Vector::new_vec3D(0.0, 0.0, 0.5), saved_ray.direction);
+    }
+
+    #[test]
+    fn normal_on_a_translated_custom_shape() {
+        let object = Object::new(
+            Shape::custom_shape(TestShape::default()),
+            translation(0.0, 1.0, 0.0),
+        );
+
+        let normal = object.normal_at(&Point::new_point3D(0.0, 1.70711, -0.70711));
+
+        assert_eq!(Vector::new_vec3D(0.0, 0.70711, -0.70711), normal);
+    }
+
+    #[test]
+    fn uv_of_a_custom_shape_is_taken_in_object_space() {
+        let object = Object::new(
+            Shape::custom_shape(TestShape::default()),
+            translation(1.0, 0.0, 0.0),
+        );
+
+        let (u, v) = object.uv_at(&Point::new_point3D(1.25, 0.0, 0.5));
+
+        assert_eq!(true, u.approx_eq(&0.25));
+        assert_eq!(true, v.approx_eq(&0.5));
+    }
+
+    #[test]
+    fn custom_shapes_are_equal_only_to_themselves() {
+        let shape = Shape::custom_shape(TestShape::default());
+
+        assert_eq!(shape.clone(), shape);
+        assert_ne!(Shape::custom_shape(TestShape::default()), shape);
+    }
+
+    #[test]
+    fn builtin_shapes_implement_the_trait() {
+        let sphere = Sphere::new();
+        let plane = Plane::new();
+        let shapes: Vec<&dyn LocalShape> = vec![&sphere, &plane];
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(Some(vec![4.0, 6.0]), shapes[0].local_intersect(&ray));
+        assert_eq!(None, shapes[1].local_intersect(&ray));
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            shapes[1].local_normal_at(&Point::new_point3D(3.0, 0.0, -2.0))
+        );
+    }
+
+    #[test]
+    fn sphere_bounds_and_uv() {
+        let sphere = Sphere::new();
+
+        assert_eq!(
+            Bounds::new(
+                Point::new_point3D(-1.0, -1.0, -1.0),
+                Point::new_point3D(1.0, 1.0, 1.0),
+            ),
+            sphere.bounds()
+        );
+        assert_eq!(
+            (0.0, 0.5),
+            sphere.local_uv(&Point::new_point3D(0.0, 0.0, -1.0))
+        );
+    }
+
+    #[test]
+    fn plane_bounds_are_infinite_along_x_and_z() {
+        let bounds = Plane::new().bounds();
+
+        assert_eq!(false, bounds.is_finite());
+        assert_eq!(true, bounds.contains(&Point::new_point3D(1e9, 0.0, -1e9)));
+        assert_eq!(false, bounds.contains(&Point::new_point3D(0.0, 0.1, 0.0)));
+    }
+
+    #[test]
+    fn object_bounds_follow_its_transformation() {
+        let object = Object::new_sphere()
+            .with_transformation(translation(1.0, 0.0, 0.0) * scaling(2.0, 1.0, 1.0));
+
+        assert_eq!(
+            Bounds::new(
+                Point::new_point3D(-1.0, -1.0, -1.0),
+                Point::new_point3D(3.0, 1.0, 1.0),
+            ),
+            object.bounds()
+        );
+    }
+
+    #[test]
+    fn rotated_bounds_enclose_every_corner() {
+        let bounds = Sphere::new()
+            .bounds()
+            .transform(&rotation_y(std::f64::consts::PI / 4.0));
+        let half_diagonal = 2.0f64.sqrt();
+
+        assert_eq!(true, bounds.max[0].approx_eq(&half_diagonal));
+        assert_eq!(true, bounds.min[2].approx_eq(&-half_diagonal));
+        assert_eq!(true, bounds.max[1].approx_eq(&1.0));
+    }
+
+    #[test]
+    fn transformed_infinite_bounds_stay_infinite() {
+        let bounds = Object::new_plane()
+            .with_transformation(rotation_z(1.0))
+            .bounds();
+
+        assert_eq!(false, bounds.is_finite());
+        assert_eq!(true, bounds.contains(&Point::new_point3D(0.0, 1e9, 0.0)));
+    }
+
+    #[test]
+    fn merging_and_expanding_bounds() {
+        let a = Sphere::new().bounds();
+        let b = Bounds::new(
+            Point::new_point3D(0.0, 2.0, 0.0),
+            Point::new_point3D(3.0, 4.0, 0.5),
+        );
+
+        assert_eq!(
+            Bounds::new(
+                Point::new_point3D(-1.5, -1.5, -1.5),
+                Point::new_point3D(3.5, 4.5, 1.5),
+            ),
+            a.merge(&b).expand(0.5)
+        );
+    }
+
+    #[test]
+    fn clipping_a_ray_to_bounds() {
+        let bounds = Sphere::new().bounds();
+        let hit = Ray::new(
+            Point::new_point3D(0.5, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+        let miss = Ray::new(
+            Point::new_point3D(2.0, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+
+        assert_eq!(Some((4.0, 6.0)), bounds.intersect(&hit));
+        assert_eq!(None, bounds.intersect(&miss));
+    }
+}
@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod stl_test {
+    use rtc::loaders::stl::parse_stl;
+    use rtc::loaders::{load_stl, MeshError};
+    use rtc::{Object, Point, Ray, Vector};
+
+    const ASCII: &str = "solid square
+  facet normal 0 1 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 1
+      vertex 1 0 0
+    endloop
+  endfacet
+  facet normal 0 1 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 1
+      vertex 1 0 1
+    endloop
+  endfacet
+endsolid square
+";
+
+    fn down_at(x: f64, z: f64) -> Ray {
+        Ray::new(
+            Point::new_point3D(x, 5.0, z),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        )
+    }
+
+    //Binary STL of the same square, with a header that starts with
+    // "solid" to make sure it is not mistaken for ascii.
+    fn binary() -> Vec<u8> {
+        let mut bytes = b"solid but actually binary".to_vec();
+        bytes.resize(80, 0);
+        bytes.extend(2u32.to_le_bytes());
+        for triangle in [
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 1.0], [1.0, 0.0, 0.0]],
+            [[0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 1.0]],
+        ] {
+            for value in [0.0f32, 1.0, 0.0] {
+                bytes.extend(value.to_le_bytes());
+            }
+            for corner in triangle {
+                for value in corner {
+                    bytes.extend((value as f32).to_le_bytes());
+                }
+            }
+            bytes.extend(0u16.to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn ascii_stl_merges_shared_vertices() {
+        let mesh = parse_stl(ASCII.as_bytes()).unwrap();
+
+        assert_eq!(4, mesh.positions().len());
+        assert_eq!(2, mesh.triangles().len());
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.7, 0.2)));
+        assert_eq!(Some(vec![5.0]), mesh.intersect(&down_at(0.2, 0.7)));
+    }
+
+    #[test]
+    fn binary_stl() {
+        let mesh = parse_stl(&binary()).unwrap();
+
+        assert_eq!(4, mesh.positions().len());
+        assert_eq!(vec![[0, 1, 2], [0, 3, 1]], mesh.triangles().to_vec());
+        assert_eq!(Point::new_point3D(0.0, 0.0, 1.0), mesh.positions()[3]);
+    }
+
+    #[test]
+    fn loading_an_stl_into_an_object() {
+        let path = std::env::temp_dir().join("rtc_stl_test.stl");
+        std::fs::write(&path, binary()).unwrap();
+        let object = Object::new_mesh(load_stl(&path).unwrap());
+
+        assert_eq!(Some(vec![5.0]), object.intersects(&down_at(0.5, 0.5)));
+        assert_eq!(
+            1.0,
+            object.normal_at(&Point::new_point3D(0.5, 0.0, 0.25))[1].abs()
+        );
+    }
+
+    #[test]
+    fn malformed_stl_files() {
+        let mut short = binary();
+        short.truncate(100);
+        let two_corners = ASCII.replacen("      vertex 1 0 0\n", "", 1);
+
+        assert_eq!(true, matches!(parse_stl(&short), Err(MeshError::Format(_))));
+        assert_eq!(
+            true,
+            matches!(parse_stl(two_corners.as_bytes()), Err(MeshError::Format(_)))
+        );
+        assert_eq!(
+            true,
+            matches!(
+                parse_stl(b"solid x\nvertex 1 2\n"),
+                Err(MeshError::Format(_))
+            )
+        );
+        let cut_in_a_facet = &ASCII[..ASCII.rfind("      vertex 1 0 1").unwrap()];
+        let stray_vertex = ASCII.replacen("endsolid", "vertex 2 0 2\nendsolid", 1);
+        for text in [cut_in_a_facet, stray_vertex.as_str()] {
+            assert_eq!(
+                true,
+                matches!(parse_stl(text.as_bytes()), Err(MeshError::Format(_)))
+            );
+        }
+        assert_eq!(
+            true,
+            matches!(
+                load_stl(std::env::temp_dir().join("rtc_missing.stl")),
+                Err(MeshError::Io(_))
+            )
+        );
+    }
+}
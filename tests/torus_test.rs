@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod torus_test {
+    use rtc::transformation::*;
+    use rtc::{ApproximateEq, LocalShape, Object, Point, Ray, Torus, Vector};
+
+    fn torus() -> Torus {
+        Torus::new(1.0, 0.25)
+    }
+
+    fn assert_hits(expected: &[f64], hits: Option<Vec<f64>>) {
+        let hits = hits.expect("the ray should hit the torus");
+        assert_eq!(expected.len(), hits.len(), "hits: {:?}", hits);
+        for (e, t) in expected.iter().zip(&hits) {
+            assert_eq!(true, e.approx_eq(t), "expected {} got {}", e, t);
+        }
+    }
+
+    #[test]
+    fn ray_through_both_sides_of_the_tube() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.0, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        assert_hits(&[3.75, 4.25, 5.75, 6.25], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_down_through_the_hole_misses() {
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 5.0, 0.0),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        );
+
+        assert_eq!(None, torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_down_through_the_tube() {
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 5.0, -1.0),
+            Vector::new_vec3D(0.0, -1.0, 0.0),
+        );
+
+        assert_hits(&[4.75, 5.25], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_from_inside_the_tube() {
+        let ray = Ray::new(
+            Point::new_point3D(1.0, 0.0, 0.0),
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+        );
+
+        assert_hits(&[-0.25, 0.25], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_grazing_the_top_of_the_tube() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.25, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        assert_hits(&[4.0, 4.0, 6.0, 6.0], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_just_above_the_tube_misses() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.25 + 1.0e-6, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        assert_eq!(None, torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_just_below_the_top_of_the_tube() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.25 - 1.0e-6, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+        let hits = torus().intersect(&ray).unwrap();
+
+        assert_eq!(4, hits.len());
+        // the chord through the tube is 2 * sqrt(r^2 - (r - 1e-6)^2) long
+        let chord = 2.0 * (2.0 * 0.25 * 1.0e-6 - 1.0e-12f64).sqrt();
+        assert_eq!(true, (hits[1] - hits[0] - chord).abs() < 1.0e-8);
+        assert_eq!(true, hits[0].approx_eq_low(&4.0));
+        assert_eq!(true, hits[3].approx_eq_low(&6.0));
+    }
+
+    #[test]
+    fn ray_grazing_the_outer_edge() {
+        let ray = Ray::new(
+            Point::new_point3D(1.25, -5.0, 0.0),
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+        );
+
+        assert_hits(&[5.0, 5.0], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn ray_tangent_to_the_inner_edge() {
+        let ray = Ray::new(
+            Point::new_point3D(-5.0, 0.0, 0.75),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        // enters the tube at x = -1, touches the inner edge at x = 0 from
+        // the inside and leaves at x = 1
+        assert_hits(&[4.0, 5.0, 5.0, 6.0], torus().intersect(&ray));
+    }
+
+    #[test]
+    fn distant_ray_keeps_its_precision() {
+        let ray = Ray::new(
+            Point::new_point3D(-1.0e4, 0.0, 0.0),
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+        );
+
+        assert_hits(
+            &[1.0e4 - 1.25, 1.0e4 - 0.75, 1.0e4 + 0.75, 1.0e4 + 1.25],
+            torus().intersect(&ray),
+        );
+    }
+
+    #[test]
+    fn diagonal_ray_hits_lie_on_the_surface() {
+        let ray = Ray::new(
+            Point::new_point3D(-3.0, 2.0, -4.0),
+            Vector::new_vec3D(4.0, -2.0, 4.0),
+        );
+        let hits = torus().intersect(&ray).unwrap();
+
+        assert_eq!(false, hits.is_empty());
+        for t in hits {
+            let point = ray.position(t);
+            let ring = (point[0].powi(2) + point[2].powi(2)).sqrt() - 1.0;
+            assert_eq!(
+                true,
+                (ring.powi(2) + point[1].powi(2) - 0.0625).abs() < 1.0e-9
+            );
+        }
+    }
+
+    #[test]
+    fn normals_on_the_tube() {
+        let torus = torus();
+
+        assert_eq!(
+            Vector::new_vec3D(1.0, 0.0, 0.0),
+            torus
+                .normal_at(&Point::new_point3D(1.25, 0.0, 0.0))
+                .normalize()
+        );
+        assert_eq!(
+            Vector::new_vec3D(-1.0, 0.0, 0.0),
+            torus
+                .normal_at(&Point::new_point3D(0.75, 0.0, 0.0))
+                .normalize()
+        );
+        assert_eq!(
+            Vector::new_vec3D(0.0, 1.0, 0.0),
+            torus
+                .normal_at(&Point::new_point3D(0.0, 0.25, 1.0))
+                .normalize()
+        );
+        let diagonal = 0.25 / 2.0f64.sqrt();
+        assert_eq!(
+            Vector::new_vec3D(0.0, -2.0f64.sqrt() / 2.0, -2.0f64.sqrt() / 2.0),
+            torus
+                .normal_at(&Point::new_point3D(0.0, -diagonal, -1.0 - diagonal))
+                .normalize()
+        );
+    }
+
+    #[test]
+    fn normal_on_a_transformed_torus() {
+        let object = Object::new_torus(1.0, 0.25)
+            .with_transformation(rotation_x(std::f64::consts::PI / 2.0));
+
+        // the tube's top now faces -z
+        let normal = object.normal_at(&Point::new_point3D(1.0, 0.0, 0.25));
+
+        assert_eq!(Vector::new_vec3D(0.0, 0.0, 1.0), normal);
+    }
+
+    #[test]
+    fn bounds_and_uv() {
+        let torus = torus();
+
+        assert_eq!(
+            true,
+            torus
+                .bounds()
+                .contains(&Point::new_point3D(1.25, 0.25, -1.25))
+        );
+        assert_eq!(
+            false,
+            torus.bounds().contains(&Point::new_point3D(0.0, 0.3, 0.0))
+        );
+
+        let (u, v) = torus.local_uv(&Point::new_point3D(0.0, 0.25, 1.0));
+        assert_eq!(true, u.approx_eq(&0.5));
+        assert_eq!(true, v.approx_eq(&0.25));
+    }
+}
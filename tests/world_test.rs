@@ -1,9 +1,30 @@
 #[cfg(test)]
 mod world_test {
     use rtc::{
-        color::Color, intersection::Computations, transformation::translation, Intersection,
-        Intersections, Light, Object, Point, Ray, Vector, World,
+        color::{Channel, Color},
+        intersection::Computations,
+        transformation::{rotation_x, scaling, translation},
+        Intersection, Intersections, Light, Object, Pattern, PatternType, Point, Ray, Vector,
+        World,
     };
+    use std::f64::consts::PI;
+
+    //A glass sphere that only transmits light in front of a white, ambient lit wall.
+    fn glass_in_front_of_wall(radius: f64) -> World {
+        let mut glass =
+            Object::new_glass_sphere().with_transformation(scaling(radius, radius, radius));
+        glass.material.ambient = 0.0;
+        glass.material.specular = 0.0;
+        glass.material.absorption = Color::new(0.5, 0.0, 0.0);
+
+        let mut wall = Object::new_plane()
+            .with_transformation(translation(0.0, 0.0, 10.0) * rotation_x(PI / 2.0));
+        wall.material.ambient = 1.0;
+        wall.material.specular = 0.0;
+
+        let light = Light::new(Color::white(), Point::new_point3D(-10.0, 10.0, -10.0));
+        World::new(light, vec![glass, wall], 5)
+    }
 
     #[test]
     fn shading_intersection() {
@@ -113,7 +134,7 @@ mod world_test {
         );
 
         let i = Intersection::new(sqrt2, &object);
-        world.push_object(object);
+        world.push_object(object.clone());
         let comps = i.prepare_computation(&ray);
         let result = world.reflected_color(&comps, world.reflection_limit);
 
@@ -139,7 +160,7 @@ mod world_test {
         );
 
         let i = Intersection::new(sqrt2, &object);
-        world.push_object(object);
+        world.push_object(object.clone());
         let comps = i.prepare_computation(&ray);
         let result = world.reflected_color(&comps, world.reflection_limit);
 
@@ -263,4 +284,148 @@ mod world_test {
 
         assert_eq!(expected, color);
     }
+
+    #[test]
+    fn refracted_color_is_absorbed_along_the_path_inside_the_medium() {
+        let world = glass_in_front_of_wall(1.0);
+        let glass = world.get_object(0).unwrap();
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+        let mut inters = Intersections::new();
+        inters.add(Intersection::new(4.0, glass));
+        inters.add(Intersection::new(6.0, glass));
+
+        let comp = Computations::prepare_computation(&inters, 0, &ray);
+        let color = world.refracted_color(&comp, world.reflection_limit);
+        let expected = Color::new((-1.0f64).exp(), 1.0, 1.0);
+
+        assert_eq!(expected, color);
+    }
+
+    #[test]
+    fn thicker_glass_absorbs_more_light() {
+        let ray = Ray::new(
+            Point::new_point3D(0.0, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+
+        let thin = glass_in_front_of_wall(0.5);
+        let thick = glass_in_front_of_wall(2.0);
+        let thin_color = thin.color_at(&ray, thin.reflection_limit);
+        let thick_color = thick.color_at(&ray, thick.reflection_limit);
+
+        assert_eq!(Color::new((-0.5f64).exp(), 1.0, 1.0), thin_color);
+        assert_eq!(Color::new((-2.0f64).exp(), 1.0, 1.0), thick_color);
+    }
+
+    //A glass sphere in front of a wall whose color changes along x,
+    // so rays bent by different angles land on different colors.
+    fn prism_in_front_of_gradient(refractive_index: f64, abbe_number: f64) -> World {
+        let mut glass = Object::new_glass_sphere();
+        glass.material.ambient = 0.0;
+        glass.material.specular = 0.0;
+        glass.material.refractive_index = refractive_index;
+        glass.material.abbe_number = abbe_number;
+
+        let mut wall = Object::new_plane()
+            .with_transformation(translation(0.0, 0.0, 10.0) * rotation_x(PI / 2.0))
+            .with_pattern(Pattern::with_type(PatternType::gradient_pattern(
+                Color::black(),
+                Color::white(),
+            )));
+        wall.material.ambient = 1.0;
+        wall.material.specular = 0.0;
+        wall.material.pattern.set_transformation(
+            translation(-10.0, 0.0, 0.0) * scaling(20.0, 1.0, 1.0),
+        );
+
+        let light = Light::new(Color::white(), Point::new_point3D(-10.0, 10.0, -10.0));
+        World::new(light, vec![glass, wall], 5)
+    }
+
+    #[test]
+    fn dispersion_traces_each_channel_with_its_own_index() {
+        let ray = Ray::new(
+            Point::new_point3D(0.4, 0.0, -5.0),
+            Vector::new_vec3D(0.0, 0.0, 1.0),
+        );
+        let prism = prism_in_front_of_gradient(1.5, 20.0);
+        let color = prism.color_at(&ray, prism.reflection_limit);
+        let glass = &prism.get_object(0).unwrap().material;
+
+        for channel in Channel::ALL {
+            let index = glass.refractive_index_at(channel.wavelength());
+            let single = prism_in_front_of_gradient(index, 0.0);
+            let expected = single.color_at(&ray, single.reflection_limit);
+
+            assert_eq!(expected.channel(channel), color.channel(channel));
+        }
+
+        assert_eq!(true, color.red > color.blue);
+    }
+
+    #[test]
+    fn transparent_objects_cast_tinted_shadows() {
+        let mut world = World::default_test();
+        let ob1 = world.get_mut_object(0).unwrap();
+        ob1.material.transparency = 0.5;
+        ob1.material.color = Color::new(1.0, 0.5, 0.0);
+        let ob2 = world.get_mut_object(1).unwrap();
+        ob2.material.transparency = 1.0;
+
+        let p = Point::new_point3D(10.0, -10.0, 10.0);
+        let expected = Color::new(0.5, 0.25, 0.0);
+
+        assert_eq!(expected, world.shadow_transmittance(&p));
+        assert_eq!(false, world.is_shadowed(&p));
+    }
+
+    #[test]
+    fn absorbing_objects_dim_the_light_by_their_thickness() {
+        let mut world = World::default_test();
+        let ob1 = world.get_mut_object(0).unwrap();
+        ob1.material.transparency = 1.0;
+        ob1.material.color = Color::white();
+        ob1.material.absorption = Color::new(0.5, 0.0, 0.0);
+        let ob2 = world.get_mut_object(1).unwrap();
+        ob2.material.transparency = 1.0;
+        ob2.material.color = Color::white();
+
+        //through the whole sphere, and from its center out
+        let outside = Point::new_point3D(10.0, -10.0, 10.0);
+        let center = Point::new_point3D(0.0, 0.0, 0.0);
+
+        assert_eq!(
+            Color::new(f64::exp(-1.0), 1.0, 1.0),
+            world.shadow_transmittance(&outside)
+        );
+        assert_eq!(
+            Color::new(f64::exp(-0.5), 1.0, 1.0),
+            world.shadow_transmittance(&center)
+        );
+    }
+
+    #[test]
+    fn opaque_objects_block_the_light() {
+        let world = World::default_test();
+        let p = Point::new_point3D(10.0, -10.0, 10.0);
+
+        assert_eq!(Color::black(), world.shadow_transmittance(&p));
+    }
+
+    #[test]
+    fn objects_that_dont_cast_shadows_let_the_light_through() {
+        let mut world = World::default_test();
+        world.objects = world
+            .objects
+            .into_iter()
+            .map(|object| object.with_casts_shadow(false))
+            .collect();
+        let p = Point::new_point3D(10.0, -10.0, 10.0);
+
+        assert_eq!(Color::white(), world.shadow_transmittance(&p));
+        assert_eq!(false, world.is_shadowed(&p));
+    }
 }